@@ -0,0 +1,47 @@
+//! Time of BalKnap and MinKnap on a strongly correlated instance, where the balanced operations
+//! keep fewer states than the alternating ones of MinKnap
+//!
+//! Run with `cargo bench --bench balknap_correlated`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use knapsack::{BalKnapSolver, KnapsackInput, KnapsackItem, KnapsackSolver, MinKnapSolver};
+
+const ITEMS: usize = 2_000;
+const RANGE: u64 = 1_000;
+
+/// Strongly correlated instance (profit = weight + RANGE / 10), with pseudo-random weights in
+/// [1, RANGE] and half of the total weight as the capacity
+fn correlated_input() -> KnapsackInput {
+    let mut seed = 0x5eed_u64;
+    let weights: Vec<u64> = (0..ITEMS)
+        .map(|_| {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 33) % RANGE + 1
+        })
+        .collect();
+    let capacity = weights.iter().sum::<u64>() / 2;
+    let items = weights
+        .iter()
+        .map(|&weight| KnapsackItem::new(weight, weight + RANGE / 10))
+        .collect();
+
+    KnapsackInput::new(items, capacity, 1).unwrap()
+}
+
+fn balknap_correlated(c: &mut Criterion) {
+    let input = correlated_input();
+    let mut group = c.benchmark_group("correlated");
+
+    group.bench_function("balknap", |b| {
+        b.iter(|| BalKnapSolver.solve(black_box(&input)))
+    });
+    group.bench_function("minknap", |b| {
+        b.iter(|| MinKnapSolver.solve(black_box(&input)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, balknap_correlated);
+criterion_main!(benches);
@@ -0,0 +1,31 @@
+//! Time of filling the whole DP table when the profits are concentrated in the last items
+//!
+//! Each row is only filled up to the total profit of the items seen so far, so the rows of the
+//! low profit items are filled in a fraction of the time of the last ones.
+//!
+//! Run with `cargo bench --bench dp_table`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use knapsack::{DpSolver, KnapsackInput, KnapsackItem};
+
+const ITEMS: u64 = 200;
+
+/// Instance whose profits grow quadratically with the index of the item
+fn increasing_profits_input() -> KnapsackInput {
+    let items = (1..=ITEMS)
+        .map(|i| KnapsackItem::new(i % 50 + 1, i * i / 100 + 1))
+        .collect();
+
+    KnapsackInput::new(items, ITEMS * 10, 1).unwrap()
+}
+
+fn dp_table(c: &mut Criterion) {
+    let input = increasing_profits_input();
+
+    c.bench_function("dp table increasing profits", |b| {
+        b.iter(|| DpSolver.build_table(black_box(&input)))
+    });
+}
+
+criterion_group!(benches, dp_table);
+criterion_main!(benches);
@@ -0,0 +1,67 @@
+//! Peak memory and time of FPTAS on a 100k item instance, allocating the scaled items for every
+//! solve or reusing a scratch buffer across the solves
+//!
+//! Run with `cargo bench --bench fptas_scratch`. The peak memory is printed before the timings.
+
+#[path = "../src/mem_profile.rs"]
+mod mem_profile;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use knapsack::{FptasDpSolver, FptasScratch, KnapsackInput, KnapsackItem, KnapsackSolver};
+
+const ITEMS: usize = 100_000;
+const CAPACITY: u64 = 50;
+const GRANULARITY: u32 = 10;
+
+/// Uncorrelated instance with pseudo-random weights in [1, 10] and profits in [1, 1000], whose
+/// small capacity keeps the (weight indexed) table of the scaled problem small
+fn large_input() -> KnapsackInput {
+    let mut seed = 0x5eed_u64;
+    let mut next = move |range: u64| {
+        seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (seed >> 33) % range + 1
+    };
+    let items = (0..ITEMS)
+        .map(|_| {
+            let weight = next(10);
+            KnapsackItem::new(weight, next(1000))
+        })
+        .collect();
+
+    KnapsackInput::new(items, CAPACITY, GRANULARITY).unwrap()
+}
+
+fn fptas_scratch(c: &mut Criterion) {
+    let input = large_input();
+    let mut scratch = FptasScratch::default();
+    // Fill the buffer once, as in a batch of solves
+    FptasDpSolver
+        .try_solve_with_scratch(&input, &mut scratch)
+        .unwrap();
+
+    let (_, fresh_peak) = mem_profile::measure_peak(|| FptasDpSolver.solve(&input));
+    let (_, scratch_peak) = mem_profile::measure_peak(|| {
+        FptasDpSolver
+            .try_solve_with_scratch(&input, &mut scratch)
+            .unwrap()
+    });
+    println!(
+        "fptas 100k: peak memory of {fresh_peak} bytes, {scratch_peak} bytes with a scratch buffer"
+    );
+
+    c.bench_function("fptas 100k", |b| {
+        b.iter(|| FptasDpSolver.solve(black_box(&input)))
+    });
+    c.bench_function("fptas 100k scratch", |b| {
+        b.iter(|| {
+            FptasDpSolver
+                .try_solve_with_scratch(black_box(&input), &mut scratch)
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, fptas_scratch);
+criterion_main!(benches);
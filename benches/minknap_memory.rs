@@ -0,0 +1,48 @@
+//! Peak memory and time of MinKnap on a strongly correlated instance, on which the core holds
+//! many states
+//!
+//! Run with `cargo bench --bench minknap_memory`. The peak memory is printed before the timings.
+
+#[path = "../src/mem_profile.rs"]
+mod mem_profile;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use knapsack::{KnapsackInput, KnapsackItem, KnapsackSolver, MinKnapSolver};
+
+const ITEMS: usize = 2_000;
+const RANGE: u64 = 1_000;
+
+/// Strongly correlated instance (profit = weight + RANGE / 10), with pseudo-random weights in
+/// [1, RANGE] and half of the total weight as the capacity
+fn correlated_input() -> KnapsackInput {
+    let mut seed = 0x5eed_u64;
+    let weights: Vec<u64> = (0..ITEMS)
+        .map(|_| {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 33) % RANGE + 1
+        })
+        .collect();
+    let capacity = weights.iter().sum::<u64>() / 2;
+    let items = weights
+        .iter()
+        .map(|&weight| KnapsackItem::new(weight, weight + RANGE / 10))
+        .collect();
+
+    KnapsackInput::new(items, capacity, 1).unwrap()
+}
+
+fn minknap_memory(c: &mut Criterion) {
+    let input = correlated_input();
+
+    let (_, peak_bytes) = mem_profile::measure_peak(|| MinKnapSolver.solve(&input));
+    println!("minknap correlated: peak memory of {} bytes", peak_bytes);
+
+    c.bench_function("minknap correlated", |b| {
+        b.iter(|| MinKnapSolver.solve(black_box(&input)))
+    });
+}
+
+criterion_group!(benches, minknap_memory);
+criterion_main!(benches);
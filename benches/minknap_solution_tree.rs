@@ -0,0 +1,52 @@
+//! Growth of the MinKnap solution tree with the size of a strongly correlated instance
+//!
+//! Run with `cargo bench --features metrics --bench minknap_solution_tree`. The memory reserved
+//! for the fragments of the tree is printed for each size before the timings.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use knapsack::{KnapsackInput, KnapsackItem, MinKnapSolver};
+
+const SIZES: [usize; 3] = [1_000, 2_000, 4_000];
+const RANGE: u64 = 1_000;
+
+/// Strongly correlated instance (profit = weight + RANGE / 10), with pseudo-random weights in
+/// [1, RANGE] and half of the total weight as the capacity
+fn correlated_input(items: usize) -> KnapsackInput {
+    let mut seed = 0x5eed_u64;
+    let weights: Vec<u64> = (0..items)
+        .map(|_| {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 33) % RANGE + 1
+        })
+        .collect();
+    let capacity = weights.iter().sum::<u64>() / 2;
+    let items = weights
+        .iter()
+        .map(|&weight| KnapsackItem::new(weight, weight + RANGE / 10))
+        .collect();
+
+    KnapsackInput::new(items, capacity, 1).unwrap()
+}
+
+fn minknap_solution_tree(c: &mut Criterion) {
+    let mut group = c.benchmark_group("minknap solution tree");
+    for size in SIZES {
+        let input = correlated_input(size);
+
+        let (_, metrics) = MinKnapSolver.solve_with_metrics(&input);
+        println!(
+            "minknap correlated {}: {} bytes reserved for the solution tree",
+            size, metrics.solution_tree_bytes
+        );
+
+        group.bench_with_input(BenchmarkId::from_parameter(size), &input, |b, input| {
+            b.iter(|| MinKnapSolver.solve_with_metrics(black_box(input)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, minknap_solution_tree);
+criterion_main!(benches);
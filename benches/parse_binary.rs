@@ -0,0 +1,42 @@
+//! Time of parsing a large instance from the text format and from the binary one
+//!
+//! Run with `cargo bench --bench parse_binary`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use knapsack::{KnapsackInput, KnapsackItem};
+use std::fmt::Write;
+
+const ITEMS: u64 = 1_000_000;
+
+fn large_input() -> KnapsackInput {
+    let items = (1..=ITEMS)
+        .map(|i| KnapsackItem::new(i % 1000 + 1, i * 7919 % 10_000))
+        .collect();
+
+    KnapsackInput::new(items, ITEMS * 250, 1).unwrap()
+}
+
+fn parse_binary(c: &mut Criterion) {
+    let input = large_input();
+
+    // The item lines have the value first, then the weight
+    let mut text = format!("{}\n{}\n\n", ITEMS, ITEMS * 250);
+    for item in &input {
+        writeln!(text, "{} {}", item.profit(), item.weight()).unwrap();
+    }
+    let mut binary = Vec::new();
+    input.write_binary(&mut binary).unwrap();
+
+    let mut group = c.benchmark_group("parse 1M items");
+    group.sample_size(10);
+    group.bench_function("text", |b| {
+        b.iter(|| KnapsackInput::parse_input(black_box(text.as_bytes())).unwrap())
+    });
+    group.bench_function("binary", |b| {
+        b.iter(|| KnapsackInput::parse_binary(black_box(binary.as_slice())).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse_binary);
+criterion_main!(benches);
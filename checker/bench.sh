@@ -4,7 +4,7 @@
 # @option -t --test-dir=./tests The directory containing the tests
 # @option -c --config=./benchmark_conf.json The configuration file for the benchmarks
 
-available_methods=("bkt" "dp" "fptas")
+available_methods=("bkt" "dp" "fptas" "min-knap" "bal-knap")
 
 RED='\033[0;31m'
 GREEN='\033[0;32m'
@@ -55,7 +55,7 @@ init() {
 
 # @cmd Benchmark a single test
 # @arg test! <TEST>   The test from the specified test directory. Eg: n500/r01000/00Uncorrelated/s000
-# @arg method![bkt|dp|fptas] <METHOD>     The method/algorithm to be used
+# @arg method![bkt|dp|fptas|min-knap|bal-knap] <METHOD>     The method/algorithm to be used
 # @option -g --granularity=1  The granularity to be used for fptas
 benchmark_one_test() {
     init
@@ -80,7 +80,7 @@ benchmark_all() {
     for method in "${available_methods[@]}"; do
         echo "Running benchmark for $method"
 
-        method_info="$(jq -r ".$method" $argc_config)"
+        method_info="$(jq -r ".[\"$method\"]" $argc_config)"
 
         tests="$(echo "$method_info" | jq -r ".inputs.[]")"
 
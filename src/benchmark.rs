@@ -1,14 +1,19 @@
 use core::str;
 use std::{
+    collections::HashMap,
+    env, fs,
     fs::File,
     io::{self, BufReader},
-    path::Path,
-    time::Duration,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use criterion::{black_box, Criterion};
-use knapsack::{KnapsackInput, KnapsackSolver};
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use knapsack::{KnapsackInput, KnapsackMethod, KnapsackSolver};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -42,6 +47,21 @@ pub struct KnapsackBenchResult {
     mean: MetricEstimation,
     median: MetricEstimation,
     std_dev: MetricEstimation,
+    /// The raw measurements of criterion, only included when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    samples: Option<RawSamples>,
+    /// Peak number of bytes allocated while solving the input once
+    #[cfg(feature = "mem-profile")]
+    #[serde(default)]
+    peak_bytes: u64,
+}
+
+/// The measurements of every sample: the i-th sample ran the solver `iters[i]` times, taking
+/// `times[i]` nanoseconds in total
+#[derive(Debug, Serialize, Deserialize)]
+struct RawSamples {
+    iters: Vec<f64>,
+    times: Vec<f64>,
 }
 
 #[derive(Error, Debug)]
@@ -52,12 +72,22 @@ pub enum BenchmarkError {
     Serde(#[from] serde_json::Error),
 }
 
+/// Criterion reports fractional nanoseconds, which are rounded to the nearest nanosecond
+///
+/// Trivial inputs can be solved in less than a nanosecond per iteration, so the positive
+/// measurements are clamped to at least 1 ns instead of being reported as 0. Invalid measurements
+/// (negative or NaN) are reported as 0.
 fn deserialize_nanos<'de, D>(deserializer: D) -> Result<Duration, D::Error>
 where
     D: Deserializer<'de>,
 {
     let nanos = f64::deserialize(deserializer)?;
-    Ok(Duration::from_nanos(nanos.floor() as u64))
+    let nanos = if nanos > 0.0 {
+        nanos.round().max(1.0) as u64
+    } else {
+        0
+    };
+    Ok(Duration::from_nanos(nanos))
 }
 
 fn serialize_nanos<S>(nanos: &Duration, serializer: S) -> Result<S::Ok, S::Error>
@@ -67,29 +97,117 @@ where
     serializer.serialize_u64(nanos.as_nanos() as u64)
 }
 
-fn extract_results(path: impl AsRef<Path>) -> Result<KnapsackBenchResult, BenchmarkError> {
-    let results_file = File::open(path)?;
-    let reader = BufReader::new(results_file);
-    let results = serde_json::from_reader(reader)?;
+/// Number of benchmark directories created so far, so that the benchmarks running in parallel
+/// never share a directory
+static BENCHMARK_DIR_COUNT: AtomicUsize = AtomicUsize::new(0);
 
-    Ok(results)
+/// Temporary criterion output directory, removed when dropped (even if the benchmark panics)
+struct BenchmarkDir {
+    path: PathBuf,
+}
+
+impl BenchmarkDir {
+    fn new() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos());
+        let count = BENCHMARK_DIR_COUNT.fetch_add(1, Ordering::Relaxed);
+        let path = env::temp_dir().join(format!(
+            "knapsack-bench-{}-{}-{}",
+            process::id(),
+            nanos,
+            count
+        ));
+        BenchmarkDir { path }
+    }
+}
+
+impl Drop for BenchmarkDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+fn extract_json<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T, BenchmarkError> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+    let value = serde_json::from_reader(reader)?;
+
+    Ok(value)
 }
 
+/// Benchmark the solver on the given input
+///
+/// The number of samples and the measurement time default to the criterion defaults when not
+/// provided. The sample size must be at least 10. With the `mem-profile` feature, the peak memory
+/// of a separate solve is reported as well.
+///
+/// If `raw_samples` is set, the raw measurements of every sample are included in the result.
 pub fn run_benchmark(
     solver: &dyn KnapsackSolver,
     input: &KnapsackInput,
+    sample_size: Option<usize>,
+    measurement_time: Option<Duration>,
+    raw_samples: bool,
 ) -> Result<KnapsackBenchResult, BenchmarkError> {
-    let mut criterion = Criterion::default().without_plots();
+    // Use a fresh output directory, so that the results of a previous run can never be reported
+    let output_dir = BenchmarkDir::new();
+    let mut criterion = Criterion::default()
+        .without_plots()
+        .output_directory(&output_dir.path);
+    if let Some(sample_size) = sample_size {
+        criterion = criterion.sample_size(sample_size);
+    }
+    if let Some(measurement_time) = measurement_time {
+        criterion = criterion.measurement_time(measurement_time);
+    }
 
     criterion.bench_function(solver.method().into(), |b| {
         b.iter(|| solver.solve(black_box(input)))
     });
 
-    let results_path = format!(
-        "{}/target/criterion/{}/new/estimates.json",
-        env!("CARGO_MANIFEST_DIR"),
-        solver.method().as_ref()
-    );
+    let results_dir = output_dir.path.join(solver.method().as_ref()).join("new");
+
+    let mut results: KnapsackBenchResult = extract_json(results_dir.join("estimates.json"))?;
+    if raw_samples {
+        results.samples = Some(extract_json(results_dir.join("sample.json"))?);
+    }
+
+    #[cfg(feature = "mem-profile")]
+    {
+        let (_, peak_bytes) = crate::mem_profile::measure_peak(|| solver.solve(input));
+        results.peak_bytes = peak_bytes;
+    }
+
+    Ok(results)
+}
+
+/// Benchmark every given solver on the input, each one in its own thread
+///
+/// Every benchmark uses its own criterion instance and output directory, so they are independent.
+/// The solvers still share the CPU cores and the memory bandwidth while they run, so the timings
+/// are only comparable to the ones of `run_benchmark` with at least one idle core per solver.
+pub fn run_benchmarks_parallel(
+    solvers: &[&dyn KnapsackSolver],
+    input: &KnapsackInput,
+    sample_size: Option<usize>,
+    measurement_time: Option<Duration>,
+    raw_samples: bool,
+) -> Result<HashMap<KnapsackMethod, KnapsackBenchResult>, BenchmarkError> {
+    thread::scope(|scope| {
+        let handles: Vec<_> = solvers
+            .iter()
+            .map(|&solver| {
+                scope.spawn(move || {
+                    run_benchmark(solver, input, sample_size, measurement_time, raw_samples)
+                        .map(|result| (solver.method(), result))
+                })
+            })
+            .collect();
 
-    extract_results(results_path)
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    })
 }
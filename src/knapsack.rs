@@ -1,19 +1,130 @@
+#[cfg(feature = "std")]
+pub mod analysis;
+pub mod balknap;
 pub mod bkt;
+#[cfg(feature = "tokio")]
+pub mod blocking;
+pub mod bundle;
+pub mod category;
+pub mod compare;
+pub mod dominance;
 pub mod dp;
+pub mod equal_weight;
+pub mod exclusion;
+pub mod forced;
 pub mod fptas;
+pub mod grouping;
+pub mod iddfs;
 pub mod minknap;
+pub mod multidim;
+pub mod multiple;
+pub mod rounding;
+pub mod signed;
 mod sol_tree;
+pub mod trivial;
+pub mod view;
+pub mod wide;
 
-use std::io::BufRead;
+use alloc::string::String;
+use alloc::vec::Vec;
+use bitvec::vec::BitVec;
+use core::ops::BitOr;
+#[cfg(feature = "std")]
+use std::io::{BufRead, ErrorKind, Read, Write};
+#[cfg(feature = "std")]
+use std::str::FromStr;
 
 use serde::Serialize;
 use strum_macros::{AsRefStr, IntoStaticStr};
 use thiserror::Error;
+use view::{ItemSource, KnapsackView};
+
+/// Maximum number of items for which the memory is reserved upfront when parsing, so that a huge
+/// declared item count doesn't allocate more memory than the actual items need
+#[cfg(feature = "std")]
+const MAX_PREALLOCATED_ITEMS: usize = 1 << 16;
 
 pub trait KnapsackSolver: Sync {
     fn solve(&self, input: &KnapsackInput) -> KnapsackSolution;
 
     fn method(&self) -> KnapsackMethod;
+
+    /// Human readable name of the solver, used in the logs and in the output of the CLI
+    fn name(&self) -> &'static str {
+        self.method().into()
+    }
+
+    fn capabilities(&self) -> SolverCapabilities;
+
+    /// Solve the problem, reporting the inputs the solver can't handle instead of panicking
+    ///
+    /// The default implementation assumes that the solver handles every input.
+    fn try_solve(&self, input: &KnapsackInput) -> Result<KnapsackSolution, KnapsackSolveError> {
+        Ok(self.solve(input))
+    }
+
+    /// Checks whether a solution with a total value of at least `threshold` exists
+    ///
+    /// This is the decision version of the problem, which solvers can answer without computing
+    /// the optimum. The default implementation compares the value of `solve` to the threshold.
+    fn has_solution_at_least(&self, input: &KnapsackInput, threshold: u64) -> bool {
+        self.solve(input).total_value >= threshold
+    }
+
+    /// Solve the problem, writing the selected items into `items_buf` instead of allocating a new
+    /// vector, and return the total value
+    ///
+    /// The buffer is cleared first, so one buffer can be reused for solving many instances. The
+    /// default implementation copies the items of `solve`.
+    fn solve_into(&self, input: &KnapsackInput, items_buf: &mut Vec<usize>) -> u64 {
+        let solution = self.solve(input);
+        items_buf.clear();
+        items_buf.extend_from_slice(&solution.items);
+        solution.total_value
+    }
+
+    /// Solve a problem given as a borrowed view over columnar item data
+    ///
+    /// The default implementation copies the viewed items into a `KnapsackInput`.
+    fn solve_view(&self, view: &KnapsackView) -> KnapsackSolution {
+        self.solve(&view.to_input())
+    }
+}
+
+/// Set of flags describing the kind of problems a solver can handle
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub struct SolverCapabilities(u8);
+
+impl SolverCapabilities {
+    /// The solver always returns an optimal solution
+    pub const EXACT: Self = Self(1 << 0);
+    /// The solver returns a feasible solution that is not necessarily optimal
+    pub const APPROXIMATE: Self = Self(1 << 1);
+    /// The solver handles items with zero weight
+    pub const ZERO_WEIGHT_ITEMS: Self = Self(1 << 2);
+    /// The running time of the solver is not exponential in the number of items
+    pub const SCALABLE: Self = Self(1 << 3);
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Checks whether a solver with these capabilities can handle the given input
+    pub fn can_solve(&self, input: &KnapsackInput) -> bool {
+        self.contains(Self::ZERO_WEIGHT_ITEMS) || input.items.iter().all(|item| item.weight > 0)
+    }
+}
+
+impl BitOr for SolverCapabilities {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -27,6 +138,21 @@ pub struct KnapsackInput {
     items: Vec<KnapsackItem>,
     capacity: u64,
     granularity: u32,
+    // factor by which the profits were multiplied when converted from floating point values
+    profit_scale: u64,
+    // optional label of each item (e.g. a SKU), empty when no item is labeled
+    labels: Vec<Option<String>>,
+}
+
+/// Order of the numbers on the item lines of the input
+///
+/// The value comes first in the default format, but many datasets (e.g. the OR-Library) list the
+/// weight first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColumnOrder {
+    #[default]
+    ValueWeight,
+    WeightValue,
 }
 
 impl KnapsackItem {
@@ -36,38 +162,447 @@ impl KnapsackItem {
             profit: value,
         }
     }
+
+    pub fn weight(&self) -> u64 {
+        self.weight
+    }
+
+    pub fn profit(&self) -> u64 {
+        self.profit
+    }
+
+    /// Parse an item line of the input format (see `KnapsackInput::parse_input`): its value and
+    /// its weight, separated by whitespace
+    ///
+    /// A trailing label is accepted but ignored, since the labels are stored in the input. The
+    /// errors report the line as the first one.
+    pub fn parse_line(line: &str) -> Result<KnapsackItem, KnapsackInputError> {
+        KnapsackItem::parse_numbered_line(1, line, ColumnOrder::ValueWeight).map(|(item, _)| item)
+    }
+
+    /// Parse an item line (see `parse_line`), returning the item and its label, if any
+    ///
+    /// line_idx - the (1-based) number of the line, reported in the errors
+    /// order - the order of the value and the weight on the line
+    fn parse_numbered_line(
+        line_idx: usize,
+        line: &str,
+        order: ColumnOrder,
+    ) -> Result<(KnapsackItem, Option<&str>), KnapsackInputError> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() != 2 && parts.len() != 3 {
+            return Err(KnapsackInputError::InvalidItemSpecification {
+                line: line_idx,
+                content: line.into(),
+            });
+        }
+
+        let (value_idx, weight_idx) = match order {
+            ColumnOrder::ValueWeight => (0, 1),
+            ColumnOrder::WeightValue => (1, 0),
+        };
+
+        let value: u64 =
+            parts[value_idx]
+                .parse()
+                .map_err(|_| KnapsackInputError::InvalidItemValue {
+                    line: line_idx,
+                    content: line.into(),
+                })?;
+        let weight: u64 =
+            parts[weight_idx]
+                .parse()
+                .map_err(|_| KnapsackInputError::InvalidItemWeight {
+                    line: line_idx,
+                    content: line.into(),
+                })?;
+
+        Ok((KnapsackItem::new(weight, value), parts.get(2).copied()))
+    }
 }
 
-#[derive(Debug, Clone, Serialize)]
+impl TryFrom<&str> for KnapsackItem {
+    type Error = KnapsackInputError;
+
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        KnapsackItem::parse_line(line)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize)]
 pub struct KnapsackSolution {
-    // index of items selected
+    // index of items selected, sorted in ascending order
     pub items: Vec<usize>,
     // total value/profit of items selected
     pub total_value: u64,
 }
 
+/// Bracket of the optimal value given by a (possibly approximate) solution and an upper bound
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct OptimalityGap {
+    // value of the solution, a lower bound of the optimum
+    pub value: u64,
+    // upper bound of the optimum
+    pub upper_bound: u64,
+}
+
+impl OptimalityGap {
+    /// Returns (upper_bound - value) / upper_bound, the maximum relative distance between the
+    /// solution and the optimum
+    pub fn relative_gap(&self) -> f64 {
+        if self.upper_bound == 0 {
+            return 0.0;
+        }
+        (self.upper_bound - self.value) as f64 / self.upper_bound as f64
+    }
+}
+
+impl KnapsackSolution {
+    /// Returns how far this solution can be from the optimum, using the LP bound of the input
+    pub fn optimality_gap(&self, input: &KnapsackInput) -> OptimalityGap {
+        OptimalityGap {
+            value: self.total_value,
+            upper_bound: input.lp_bound().max(self.total_value),
+        }
+    }
+
+    /// Compares only the total values of the solutions, since different solvers may select
+    /// different items for the same optimal value
+    pub fn value_eq(&self, other: &KnapsackSolution) -> bool {
+        self.total_value == other.total_value
+    }
+
+    /// Returns the total weight of the selected items of the given input
+    pub fn total_weight(&self, input: &KnapsackInput) -> u64 {
+        self.items
+            .iter()
+            .map(|&item| input.items[item].weight)
+            .sum()
+    }
+
+    /// Greedily adds the unselected items that still fit, in descending order of efficiency
+    ///
+    /// This is a cheap improvement step for the solutions of approximate or interrupted solvers.
+    /// The items only get added while they fit in the remaining capacity, so a feasible solution
+    /// stays feasible (and an infeasible one is left unchanged). The items stay sorted.
+    pub fn greedy_fill(&mut self, input: &KnapsackInput) {
+        let total_weight = self.total_weight(input);
+        if total_weight > input.capacity {
+            return;
+        }
+        let mut remaining_capacity = input.capacity - total_weight;
+
+        let mut candidates: Vec<usize> = (0..input.items.len())
+            .filter(|item| self.items.binary_search(item).is_err())
+            .filter(|&item| input.items[item].profit > 0)
+            .collect();
+        candidates.sort_by(|&a, &b| minknap::efficiency_cmp(&input.items[a], &input.items[b]));
+
+        for item in candidates {
+            let KnapsackItem { weight, profit } = input.items[item];
+            if weight <= remaining_capacity {
+                remaining_capacity -= weight;
+                self.total_value += profit;
+                self.items.push(item);
+            }
+        }
+        self.items.sort_unstable();
+    }
+
+    /// Returns the fraction of the capacity used by the selected items, between 0 and 1 for a
+    /// feasible solution
+    pub fn utilization(&self, input: &KnapsackInput) -> f64 {
+        if input.capacity == 0 {
+            return 0.0;
+        }
+        self.total_weight(input) as f64 / input.capacity as f64
+    }
+
+    /// Returns the profit per unit of weight of the selected items of the given input, or `None`
+    /// when their total weight is 0 (no items, or only zero weight items are selected)
+    pub fn efficiency(&self, input: &KnapsackInput) -> Option<f64> {
+        match self.total_weight(input) {
+            0 => None,
+            total_weight => Some(self.total_value as f64 / total_weight as f64),
+        }
+    }
+
+    /// Checks that the solution is valid for the given input: the items are distinct valid indices
+    /// sorted in ascending order, they fit in the knapsack, and their total profit is the reported
+    /// value
+    pub fn verify(&self, input: &KnapsackInput) -> Result<(), SolutionError> {
+        if let Some(&index) = self.items.iter().find(|&&index| index >= input.items.len()) {
+            return Err(SolutionError::InvalidItem { index });
+        }
+        if let Some(pair) = self.items.windows(2).find(|pair| pair[0] >= pair[1]) {
+            return Err(SolutionError::UnsortedItems { index: pair[1] });
+        }
+
+        let weight = self.items.iter().fold(0u64, |total, &item| {
+            total.saturating_add(input.items[item].weight)
+        });
+        if weight > input.capacity {
+            return Err(SolutionError::CapacityExceeded {
+                weight,
+                capacity: input.capacity,
+            });
+        }
+
+        let value: u64 = self
+            .items
+            .iter()
+            .map(|&item| input.items[item].profit)
+            .sum();
+        if value != self.total_value {
+            return Err(SolutionError::ValueMismatch {
+                reported: self.total_value,
+                actual: value,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the solution with the weight, the profit and the label of each selected item
+    /// embedded, so that it can be interpreted without the input once serialized
+    pub fn detailed(&self, input: &KnapsackInput) -> DetailedSolution {
+        DetailedSolution {
+            items: self
+                .items
+                .iter()
+                .map(|&index| DetailedItem {
+                    index,
+                    weight: input.items[index].weight,
+                    profit: input.items[index].profit,
+                    label: input.label(index).map(String::from),
+                })
+                .collect(),
+            total_value: self.total_value,
+            total_weight: self.total_weight(input),
+        }
+    }
+
+    /// Returns the labels of the selected items (see `KnapsackInput::label`), in the same order as
+    /// `items`, with None for the items that aren't labeled
+    pub fn labeled_items<'a>(&self, input: &'a KnapsackInput) -> Vec<Option<&'a str>> {
+        self.items.iter().map(|&item| input.label(item)).collect()
+    }
+
+    /// Returns the solution as a decision vector of n items, with true values for the selected items
+    ///
+    /// Panics if an item index is not lower than n.
+    pub fn decision_vector(&self, n: usize) -> BitVec {
+        let mut decision_vec = BitVec::repeat(false, n);
+        for &item in &self.items {
+            decision_vec.set(item, true);
+        }
+        decision_vec
+    }
+}
+
+/// Measures of the work done by a solver, used for complexity studies
+///
+/// The counters are only updated when the `metrics` feature is enabled, so that regular builds
+/// don't pay for them.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct SolveMetrics {
+    // number of states pushed on the stack (backtracking)
+    pub stack_pushes: u64,
+    // number of states popped from the stack (backtracking)
+    pub stack_pops: u64,
+    // number of cells of the table that were filled (dynamic programming)
+    pub table_cells: u64,
+    // peak memory reserved for the state vectors, in bytes (min-knap)
+    pub peak_state_bytes: u64,
+    // number of items explored around the break item, i.e. the size of the core (min-knap)
+    pub core_items: u64,
+    // whether the core grew to every candidate item, the worst case of min-knap
+    pub full_core: bool,
+    // memory reserved for the fragments of the solution tree, in bytes (min-knap)
+    pub solution_tree_bytes: u64,
+}
+
+impl SolveMetrics {
+    #[inline(always)]
+    pub(crate) fn record_push(&mut self) {
+        #[cfg(feature = "metrics")]
+        {
+            self.stack_pushes += 1;
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn record_pop(&mut self) {
+        #[cfg(feature = "metrics")]
+        {
+            self.stack_pops += 1;
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn record_table_cells(&mut self, _cells: u64) {
+        #[cfg(feature = "metrics")]
+        {
+            self.table_cells += _cells;
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn record_core(&mut self, _core_items: usize, _candidate_items: usize) {
+        #[cfg(feature = "metrics")]
+        {
+            self.core_items = _core_items as u64;
+            self.full_core = _core_items == _candidate_items;
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn record_state_bytes(&mut self, _bytes: u64) {
+        #[cfg(feature = "metrics")]
+        {
+            self.peak_state_bytes = self.peak_state_bytes.max(_bytes);
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn record_solution_tree_bytes(&mut self, _bytes: u64) {
+        #[cfg(feature = "metrics")]
+        {
+            self.solution_tree_bytes = _bytes;
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum KnapsackInputError {
-    #[error("Invalid item count")]
-    InvalidItemCount,
+    #[error("Invalid item count on line {line}: \"{content}\"")]
+    InvalidItemCount { line: usize, content: String },
     #[error("Missing item count")]
     MissingItemCount,
-    #[error("Invalid capacity")]
-    InvalidCapacity,
+    #[error("Invalid capacity on line {line}: \"{content}\"")]
+    InvalidCapacity { line: usize, content: String },
+    #[error("The capacity must be positive")]
+    ZeroCapacity,
     #[error("Missing capacity")]
     MissingCapacity,
+    #[error(
+        "Invalid header on line {line}: \"{content}\" (expected the item count and the capacity)"
+    )]
+    InvalidHeader { line: usize, content: String },
     #[error("Invalid granularity")]
     InvalidGranularity,
-    #[error("Invalid item weight")]
-    InvalidItemWeight,
-    #[error("Invalid item value")]
-    InvalidItemValue,
-    #[error("Invalid item specification")]
-    InvalidItemSpecification,
+    #[error("Invalid item weight on line {line}: \"{content}\"")]
+    InvalidItemWeight { line: usize, content: String },
+    #[error("Invalid item value on line {line}: \"{content}\"")]
+    InvalidItemValue { line: usize, content: String },
+    #[error("Invalid item specification on line {line}: \"{content}\"")]
+    InvalidItemSpecification { line: usize, content: String },
+    #[error("Invalid floating point profit for item {index}")]
+    InvalidFloatProfit { index: usize },
+    #[cfg(feature = "std")]
     #[error("Failed to read input")]
     ReadError(#[from] std::io::Error),
     #[error("Insufficient items provided")]
     InsufficientItems,
+    #[error("Invalid number of decimals")]
+    InvalidDecimals,
+    #[error("Item dimensions don't match the knapsack dimensions")]
+    DimensionMismatch,
+    #[error("The total profit of the items overflows")]
+    ProfitOverflow,
+    #[error("The number of weights ({weights}) doesn't match the number of profits ({profits})")]
+    ItemCountMismatch { weights: usize, profits: usize },
+    #[error("Invalid forced item {index}")]
+    InvalidForcedItem { index: usize },
+    #[error("Item {index} is both forced in and forced out")]
+    ConflictingForcedItem { index: usize },
+    #[error("The forced items exceed the capacity")]
+    InfeasibleForcedItems,
+    #[error("The scaled weights overflow")]
+    WeightOverflow,
+    #[error("The number of labels doesn't match the number of items")]
+    LabelCountMismatch,
+    #[error("Invalid bundle item {index}")]
+    InvalidBundleItem { index: usize },
+    #[error("The total weight or profit of the bundle of item {index} overflows")]
+    BundleOverflow { index: usize },
+    #[error("The number of categories doesn't match the number of items")]
+    CategoryCountMismatch,
+    #[error("Invalid weight for category {category}")]
+    InvalidCategoryWeight { category: u32 },
+    #[error("The weighted profit of item {index} exceeds 2^53, above which it would be inexact")]
+    InexactWeightedProfit { index: usize },
+    #[error("Invalid exclusion group item {index}")]
+    InvalidExclusionItem { index: usize },
+    #[error("Item {index} belongs to several exclusion groups")]
+    OverlappingExclusionGroups { index: usize },
+}
+
+/// Non-fatal issue found while parsing an input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseWarning {
+    /// More item lines than declared were found, and the extra ones were ignored
+    ExtraItemLines { declared: usize, ignored: usize },
+    /// The item has zero weight, so it is always part of the solution
+    ZeroWeightItem { item: usize },
+    /// The item is heavier than the capacity, so it can never be part of the solution
+    ItemTooHeavy { item: usize },
+}
+
+impl core::fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseWarning::ExtraItemLines { declared, ignored } => write!(
+                f,
+                "declared {declared} items but {} item lines were found ({ignored} ignored)",
+                declared + ignored
+            ),
+            ParseWarning::ZeroWeightItem { item } => write!(f, "item {item} has zero weight"),
+            ParseWarning::ItemTooHeavy { item } => {
+                write!(f, "item {item} is heavier than the capacity")
+            }
+        }
+    }
+}
+
+/// A selected item of a `DetailedSolution`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DetailedItem {
+    pub index: usize,
+    pub weight: u64,
+    pub profit: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// A solution along with the details of its items (see `KnapsackSolution::detailed`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DetailedSolution {
+    // the selected items, sorted in ascending order of their indices
+    pub items: Vec<DetailedItem>,
+    pub total_value: u64,
+    pub total_weight: u64,
+}
+
+/// Reason why a solution is invalid for an input (see `KnapsackSolution::verify`)
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SolutionError {
+    #[error("Invalid item {index}")]
+    InvalidItem { index: usize },
+    #[error("The items are not sorted in strictly ascending order (at item {index})")]
+    UnsortedItems { index: usize },
+    #[error("The total weight {weight} exceeds the capacity {capacity}")]
+    CapacityExceeded { weight: u64, capacity: u64 },
+    #[error("The reported value {reported} doesn't match the value {actual} of the items")]
+    ValueMismatch { reported: u64, actual: u64 },
+}
+
+#[derive(Error, Debug)]
+pub enum KnapsackSolveError {
+    #[error("The DP table would need {cells} cells, exceeding the limit of {limit} (consider using MinKnap instead)")]
+    TableTooLarge { cells: u128, limit: u64 },
+    #[error("The total {quantity} of the items doesn't fit in a u64")]
+    TotalOverflow { quantity: &'static str },
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, AsRefStr, IntoStaticStr)]
@@ -76,6 +611,26 @@ pub enum KnapsackMethod {
     Bkt,
     Fptas,
     MinKnap,
+    EqualWeight,
+    IddfsBkt,
+    BalKnap,
+    RandomizedRounding,
+}
+
+impl KnapsackMethod {
+    /// Every solving method, in declaration order
+    pub const fn all() -> &'static [KnapsackMethod] {
+        &[
+            KnapsackMethod::Dp,
+            KnapsackMethod::Bkt,
+            KnapsackMethod::Fptas,
+            KnapsackMethod::MinKnap,
+            KnapsackMethod::EqualWeight,
+            KnapsackMethod::IddfsBkt,
+            KnapsackMethod::BalKnap,
+            KnapsackMethod::RandomizedRounding,
+        ]
+    }
 }
 
 impl KnapsackInput {
@@ -84,53 +639,310 @@ impl KnapsackInput {
     /// The input format is as follows:
     /// n - number of items on the first line
     /// capacity - the capacity of the knapsack on the second line
-    /// n lines with two integers each, representing the value and weight of each item
+    /// n lines with two integers each, representing the value and weight of each item, optionally
+    /// followed by a label (see `KnapsackInput::label`)
+    #[cfg(feature = "std")]
     pub fn parse_input(input: impl BufRead) -> Result<KnapsackInput, KnapsackInputError> {
-        // Parse only the non-empty lines
-        let mut lines = input
+        KnapsackInput::parse_input_with_order(input, ColumnOrder::default())
+    }
+
+    /// Parse the input for the knapsack problem (see `parse_input`), with the given order of the
+    /// numbers on the item lines
+    #[cfg(feature = "std")]
+    pub fn parse_input_with_order(
+        input: impl BufRead,
+        order: ColumnOrder,
+    ) -> Result<KnapsackInput, KnapsackInputError> {
+        KnapsackInput::parse_with_warnings(input, order).map(|(input, _)| input)
+    }
+
+    /// Parse the input for the knapsack problem (see `parse_input`), also returning the non-fatal
+    /// issues found in it
+    #[cfg(feature = "std")]
+    pub fn parse_input_with_warnings(
+        input: impl BufRead,
+    ) -> Result<(KnapsackInput, Vec<ParseWarning>), KnapsackInputError> {
+        KnapsackInput::parse_with_warnings(input, ColumnOrder::default())
+    }
+
+    #[cfg(feature = "std")]
+    fn parse_with_warnings(
+        input: impl BufRead,
+        order: ColumnOrder,
+    ) -> Result<(KnapsackInput, Vec<ParseWarning>), KnapsackInputError> {
+        let mut lines = KnapsackInput::non_empty_lines(input);
+        let input = KnapsackInput::parse_instance(&mut lines, order)?;
+
+        let mut warnings = Vec::new();
+
+        let ignored_lines = lines.count();
+        if ignored_lines > 0 {
+            warnings.push(ParseWarning::ExtraItemLines {
+                declared: input.items.len(),
+                ignored: ignored_lines,
+            });
+        }
+
+        for (item, knapsack_item) in input.items.iter().enumerate() {
+            if knapsack_item.weight == 0 {
+                warnings.push(ParseWarning::ZeroWeightItem { item });
+            } else if knapsack_item.weight > input.capacity {
+                warnings.push(ParseWarning::ItemTooHeavy { item });
+            }
+        }
+
+        Ok((input, warnings))
+    }
+
+    /// Parse several concatenated instances, each one in the format described in `parse_input`,
+    /// until the end of the input
+    #[cfg(feature = "std")]
+    pub fn parse_many(input: impl BufRead) -> Result<Vec<KnapsackInput>, KnapsackInputError> {
+        KnapsackInput::parse_many_with_order(input, ColumnOrder::default())
+    }
+
+    /// Parse several concatenated instances (see `parse_many`), with the given order of the
+    /// numbers on the item lines
+    #[cfg(feature = "std")]
+    pub fn parse_many_with_order(
+        input: impl BufRead,
+        order: ColumnOrder,
+    ) -> Result<Vec<KnapsackInput>, KnapsackInputError> {
+        let mut lines = KnapsackInput::non_empty_lines(input).peekable();
+        let mut instances = Vec::new();
+
+        while lines.peek().is_some() {
+            instances.push(KnapsackInput::parse_instance(&mut lines, order)?);
+        }
+
+        Ok(instances)
+    }
+
+    /// Returns the non-empty lines of the input, along with their (1-based) line numbers
+    #[cfg(feature = "std")]
+    fn non_empty_lines(input: impl BufRead) -> impl Iterator<Item = (usize, String)> {
+        input
             .lines()
             .map_while(Result::ok)
-            .filter_map(|line| (!line.is_empty()).then_some(line));
+            .enumerate()
+            .filter_map(|(idx, line)| (!line.is_empty()).then_some((idx + 1, line)))
+    }
 
-        let n = lines
-            .next()
-            .ok_or(KnapsackInputError::MissingItemCount)?
+    /// Parse a single instance from the given lines, consuming only the lines of the instance
+    #[cfg(feature = "std")]
+    fn parse_instance(
+        lines: &mut impl Iterator<Item = (usize, String)>,
+        order: ColumnOrder,
+    ) -> Result<KnapsackInput, KnapsackInputError> {
+        let (count_line, count_content) =
+            lines.next().ok_or(KnapsackInputError::MissingItemCount)?;
+        let n: usize = count_content
             .parse()
-            .map_err(|_| KnapsackInputError::InvalidItemCount)?;
+            .map_err(|_| KnapsackInputError::InvalidItemCount {
+                line: count_line,
+                content: count_content,
+            })?;
+
+        let (capacity_line, capacity_content) =
+            lines.next().ok_or(KnapsackInputError::MissingCapacity)?;
+        let capacity =
+            capacity_content
+                .parse()
+                .map_err(|_| KnapsackInputError::InvalidCapacity {
+                    line: capacity_line,
+                    content: capacity_content,
+                })?;
+
+        KnapsackInput::parse_items(lines, n, capacity, order)
+    }
+
+    /// Parse an input in the OR-Library format
+    ///
+    /// The first line contains the number of items and the capacity of the knapsack, separated by
+    /// whitespace, and it is followed by the item lines (as in `parse_input`).
+    #[cfg(feature = "std")]
+    pub fn parse_or_library(input: impl BufRead) -> Result<KnapsackInput, KnapsackInputError> {
+        KnapsackInput::parse_or_library_with_order(input, ColumnOrder::default())
+    }
 
-        let capacity = lines
+    /// Parse an input in the OR-Library format (see `parse_or_library`), with the given order of
+    /// the numbers on the item lines
+    #[cfg(feature = "std")]
+    pub fn parse_or_library_with_order(
+        input: impl BufRead,
+        order: ColumnOrder,
+    ) -> Result<KnapsackInput, KnapsackInputError> {
+        let mut lines = KnapsackInput::non_empty_lines(input);
+
+        let (header_line, header_content) =
+            lines.next().ok_or(KnapsackInputError::MissingItemCount)?;
+        let mut header = header_content.split_whitespace();
+
+        let n: usize = header
+            .next()
+            .and_then(|count| count.parse().ok())
+            .ok_or_else(|| KnapsackInputError::InvalidItemCount {
+                line: header_line,
+                content: header_content.clone(),
+            })?;
+        let capacity = header
             .next()
             .ok_or(KnapsackInputError::MissingCapacity)?
             .parse()
-            .map_err(|_| KnapsackInputError::InvalidCapacity)?;
+            .map_err(|_| KnapsackInputError::InvalidCapacity {
+                line: header_line,
+                content: header_content.clone(),
+            })?;
+        if header.next().is_some() {
+            return Err(KnapsackInputError::InvalidHeader {
+                line: header_line,
+                content: header_content,
+            });
+        }
 
-        let mut items = Vec::with_capacity(n);
-        for line in lines.take(n) {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() != 2 {
-                return Err(KnapsackInputError::InvalidItemSpecification);
-            }
+        KnapsackInput::parse_items(&mut lines, n, capacity, order)
+    }
 
-            let value: u64 = parts[0]
-                .parse()
-                .map_err(|_| KnapsackInputError::InvalidItemValue)?;
-            let weight: u64 = parts[1]
-                .parse()
-                .map_err(|_| KnapsackInputError::InvalidItemWeight)?;
+    /// Parse an input in the packed binary format, which is much faster to load than the text
+    /// formats for huge instances
+    ///
+    /// The layout is the item count and the capacity, followed by the weight and the profit of
+    /// each item, all of them as little-endian u64 values. The reader should be buffered (e.g. a
+    /// `BufReader`), since the values are read one by one. See `write_binary` for the writing side.
+    #[cfg(feature = "std")]
+    pub fn parse_binary(mut input: impl Read) -> Result<KnapsackInput, KnapsackInputError> {
+        let n = KnapsackInput::read_u64(&mut input, KnapsackInputError::MissingItemCount)?;
+        let capacity = KnapsackInput::read_u64(&mut input, KnapsackInputError::MissingCapacity)?;
 
-            items.push(KnapsackItem::new(weight, value));
+        let mut items = Vec::with_capacity((n as usize).min(MAX_PREALLOCATED_ITEMS));
+        for _ in 0..n {
+            let weight =
+                KnapsackInput::read_u64(&mut input, KnapsackInputError::InsufficientItems)?;
+            let profit =
+                KnapsackInput::read_u64(&mut input, KnapsackInputError::InsufficientItems)?;
+            items.push(KnapsackItem::new(weight, profit));
+        }
+
+        KnapsackInput::new(items, capacity, 1)
+    }
+
+    /// Reads a little-endian u64, returning the given error if the input ends before it
+    #[cfg(feature = "std")]
+    fn read_u64(
+        input: &mut impl Read,
+        eof_error: KnapsackInputError,
+    ) -> Result<u64, KnapsackInputError> {
+        let mut bytes = [0u8; 8];
+        match input.read_exact(&mut bytes) {
+            Ok(()) => Ok(u64::from_le_bytes(bytes)),
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => Err(eof_error),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Write the input in the packed binary format read by `parse_binary`
+    ///
+    /// The labels of the items are not written. The writer should be buffered (e.g. a
+    /// `BufWriter`), since the values are written one by one.
+    #[cfg(feature = "std")]
+    pub fn write_binary(&self, mut output: impl Write) -> std::io::Result<()> {
+        output.write_all(&(self.items.len() as u64).to_le_bytes())?;
+        output.write_all(&self.capacity.to_le_bytes())?;
+        for item in &self.items {
+            output.write_all(&item.weight.to_le_bytes())?;
+            output.write_all(&item.profit.to_le_bytes())?;
+        }
+        output.flush()
+    }
+
+    /// Parse the next `n` item lines into an instance with the given capacity
+    #[cfg(feature = "std")]
+    fn parse_items(
+        lines: &mut impl Iterator<Item = (usize, String)>,
+        n: usize,
+        capacity: u64,
+        order: ColumnOrder,
+    ) -> Result<KnapsackInput, KnapsackInputError> {
+        let mut items = Vec::with_capacity(n.min(MAX_PREALLOCATED_ITEMS));
+        let mut labels = Vec::new();
+        for (line_idx, line) in lines.take(n) {
+            let (item, label) = KnapsackItem::parse_numbered_line(line_idx, &line, order)?;
+
+            // The labels are only stored once an item is labeled
+            if let Some(label) = label {
+                labels.resize(items.len(), None);
+                labels.push(Some(label.to_string()));
+            }
+
+            items.push(item);
         }
 
         if items.len() < n {
             return Err(KnapsackInputError::InsufficientItems);
         }
 
-        KnapsackInput::new(items, capacity, 1)
+        if !labels.is_empty() {
+            labels.resize(items.len(), None);
+        }
+
+        Ok(KnapsackInput {
+            labels,
+            ..KnapsackInput::new(items, capacity, 1)?
+        })
+    }
+
+    /// Parse the input from separate weights and profits streams
+    ///
+    /// Each stream contains one number per line (empty lines are ignored), and the i-th weight
+    /// and the i-th profit describe the i-th item. Both streams must have the same length.
+    #[cfg(feature = "std")]
+    pub fn parse_split(
+        weights: impl BufRead,
+        profits: impl BufRead,
+        capacity: u64,
+    ) -> Result<KnapsackInput, KnapsackInputError> {
+        let weights = Self::parse_column(weights, |line, content| {
+            KnapsackInputError::InvalidItemWeight { line, content }
+        })?;
+        let profits = Self::parse_column(profits, |line, content| {
+            KnapsackInputError::InvalidItemValue { line, content }
+        })?;
+
+        KnapsackInput::from_slices(&weights, &profits, capacity)
+    }
+
+    /// Parse a stream containing one number per non-empty line
+    #[cfg(feature = "std")]
+    fn parse_column(
+        input: impl BufRead,
+        error: impl Fn(usize, String) -> KnapsackInputError,
+    ) -> Result<Vec<u64>, KnapsackInputError> {
+        input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| line.as_ref().map_or(true, |line| !line.trim().is_empty()))
+            .map(|(idx, line)| {
+                let line = line?;
+                line.trim().parse().map_err(|_| error(idx + 1, line))
+            })
+            .collect()
+    }
+
+    /// Create an input from separate weight and profit slices, without any I/O
+    ///
+    /// This is the constructor to use when the standard library (and thus `parse_input`) is not
+    /// available.
+    pub fn from_slices(
+        weights: &[u64],
+        profits: &[u64],
+        capacity: u64,
+    ) -> Result<Self, KnapsackInputError> {
+        KnapsackView::new(weights, profits, capacity).map(|view| view.to_input())
     }
 
     fn validate_capacity(capacity: u64) -> Result<(), KnapsackInputError> {
         if capacity == 0 {
-            return Err(KnapsackInputError::InvalidCapacity);
+            return Err(KnapsackInputError::ZeroCapacity);
         }
         Ok(())
     }
@@ -154,9 +966,130 @@ impl KnapsackInput {
             items,
             capacity,
             granularity,
+            profit_scale: 1,
+            labels: Vec::new(),
+        })
+    }
+
+    /// Create an input from items with floating point profits, given as (weight, profit) pairs
+    ///
+    /// The profits are converted to fixed point by multiplying them by 10^decimals and rounding
+    /// to the nearest integer, so any precision beyond the given number of decimals is lost.
+    /// Use `to_float_profit` to convert a profit of a solution back to a floating point value.
+    pub fn from_float_items(
+        items: &[(u64, f64)],
+        capacity: u64,
+        decimals: u32,
+    ) -> Result<Self, KnapsackInputError> {
+        let profit_scale = 10u64
+            .checked_pow(decimals)
+            .ok_or(KnapsackInputError::InvalidDecimals)?;
+
+        let items = items
+            .iter()
+            .enumerate()
+            .map(|(index, &(weight, profit))| {
+                let scaled_profit = profit * profit_scale as f64;
+                if !scaled_profit.is_finite()
+                    || scaled_profit < 0.0
+                    || scaled_profit >= u64::MAX as f64
+                {
+                    return Err(KnapsackInputError::InvalidFloatProfit { index });
+                }
+                // Round to the nearest integer (the value is known to be non-negative)
+                Ok(KnapsackItem::new(weight, (scaled_profit + 0.5) as u64))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut input = KnapsackInput::new(items, capacity, 1)?;
+        input.profit_scale = profit_scale;
+
+        Ok(input)
+    }
+
+    /// Convert a profit of this input (e.g. the total value of a solution) back to the floating
+    /// point scale it was created with
+    pub fn to_float_profit(&self, profit: u64) -> f64 {
+        profit as f64 / self.profit_scale as f64
+    }
+
+    /// Returns a copy of this input with all the weights and the capacity multiplied by the given
+    /// factor (e.g. for converting kilograms to grams), which doesn't change the optimal items
+    pub fn scale_weights(&self, factor: u64) -> Result<KnapsackInput, KnapsackInputError> {
+        let capacity = self
+            .capacity
+            .checked_mul(factor)
+            .ok_or(KnapsackInputError::WeightOverflow)?;
+        Self::validate_capacity(capacity)?;
+
+        let items = self
+            .items
+            .iter()
+            .map(|item| {
+                let weight = item.weight.checked_mul(factor)?;
+                Some(KnapsackItem { weight, ..*item })
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(KnapsackInputError::WeightOverflow)?;
+
+        Ok(KnapsackInput {
+            items,
+            capacity,
+            labels: self.labels.clone(),
+            ..*self
         })
     }
 
+    /// Set the label of each item, replacing the current ones
+    ///
+    /// There must be one (optional) label per item.
+    pub fn with_labels(mut self, labels: Vec<Option<String>>) -> Result<Self, KnapsackInputError> {
+        if labels.len() != self.items.len() {
+            return Err(KnapsackInputError::LabelCountMismatch);
+        }
+        self.labels = labels;
+        Ok(self)
+    }
+
+    /// Append an item with the given weight and profit
+    pub fn push_item(&mut self, weight: u64, profit: u64) {
+        self.extend_items([KnapsackItem::new(weight, profit)]);
+    }
+
+    /// Append the given items, in order
+    ///
+    /// If the input is labeled, the new items have no label.
+    pub fn extend_items(&mut self, items: impl IntoIterator<Item = KnapsackItem>) {
+        self.items.extend(items);
+        if !self.labels.is_empty() {
+            self.labels.resize(self.items.len(), None);
+        }
+    }
+
+    /// Returns the label of the given item, if it has one
+    pub fn label(&self, item: usize) -> Option<&str> {
+        self.labels.get(item).and_then(Option::as_deref)
+    }
+
+    /// Returns the labels of the given items, in the same order, or no labels if none of the items
+    /// of this input is labeled
+    fn select_labels(&self, items: &[usize]) -> Vec<Option<String>> {
+        if self.labels.is_empty() {
+            return Vec::new();
+        }
+        items
+            .iter()
+            .map(|&item| self.labels[item].clone())
+            .collect()
+    }
+
+    /// Replace the capacity of the knapsack, which must not be 0
+    pub fn set_capacity(&mut self, capacity: u64) -> Result<(), KnapsackInputError> {
+        Self::validate_capacity(capacity)?;
+        self.capacity = capacity;
+        Ok(())
+    }
+
     pub fn set_granularity(&mut self, granularity: u32) -> Result<(), KnapsackInputError> {
         if granularity == 0 {
             return Err(KnapsackInputError::InvalidGranularity);
@@ -165,11 +1098,109 @@ impl KnapsackInput {
         Ok(())
     }
 
+    /// Returns the weight of the heaviest item, or `None` if there are no items
+    pub fn max_weight(&self) -> Option<u64> {
+        ItemSource::max_weight(self)
+    }
+
+    #[deprecated(note = "renamed to `max_weight`, since the items have weights, not costs")]
     pub fn max_cost(&self) -> u64 {
-        self.items.iter().map(|item| item.weight).max().unwrap()
+        self.max_weight().unwrap_or(0)
+    }
+
+    /// Returns the total weight of the items, saturating at `u64::MAX`
+    pub fn total_weight(&self) -> u64 {
+        ItemSource::total_weight(self)
+    }
+
+    /// Checks that the total weight and the total profit of the items fit in a `u64`, which the
+    /// search based solvers rely on for their running sums
+    pub(crate) fn check_totals(&self) -> Result<(), KnapsackSolveError> {
+        let fits = |quantity: fn(&KnapsackItem) -> u64| {
+            self.items
+                .iter()
+                .try_fold(0u64, |total, item| total.checked_add(quantity(item)))
+                .is_some()
+        };
+
+        if !fits(|item| item.weight) {
+            return Err(KnapsackSolveError::TotalOverflow { quantity: "weight" });
+        }
+        if !fits(|item| item.profit) {
+            return Err(KnapsackSolveError::TotalOverflow { quantity: "profit" });
+        }
+        Ok(())
     }
 
-    pub fn max_item_profit(&self) -> u64 {
-        self.items.iter().map(|item| u64::from(item.profit)).sum()
+    /// Returns the total profit of the items, or `None` if it overflows a `u64`
+    pub fn max_item_profit(&self) -> Option<u64> {
+        ItemSource::total_profit(self)
+    }
+
+    /// Returns the Dantzig upper bound of the problem: the optimal value of the LP relaxation,
+    /// rounded down.
+    ///
+    /// The items are taken greedily in descending order of efficiency (profit/weight), and the
+    /// first item that doesn't fit is taken fractionally.
+    pub fn lp_bound(&self) -> u64 {
+        ItemSource::lp_bound(self)
+    }
+
+    /// Returns the break item of the LP relaxation: the original index of the first item (in
+    /// descending order of efficiency) that doesn't fit entirely, and the fraction of it taken in
+    /// the LP solution, in [0, 1)
+    ///
+    /// The items heavier than the capacity are left out, as in the break solution of MinKnap.
+    /// Returns None if all the other items fit in the knapsack.
+    pub fn break_item(&self) -> Option<(usize, f64)> {
+        let (efficiency_ordering, mut decision_vec, _) =
+            minknap::prepare_items(self, &minknap::efficiency_cmp);
+        let break_solution =
+            minknap::BreakSolution::new(self, &efficiency_ordering, decision_vec.as_mut_bitslice());
+
+        let item = *efficiency_ordering.get(break_solution.break_index)?;
+        let fraction =
+            (self.capacity - break_solution.total_weight) as f64 / self.items[item].weight as f64;
+
+        Some((item, fraction))
+    }
+
+    /// Returns the efficiency (profit/weight) of the break item (see `break_item`)
+    ///
+    /// This is the dual price of the capacity in the LP relaxation: the profit one more unit of
+    /// capacity would bring to the LP solution. Returns None if there is no break item.
+    pub fn break_efficiency(&self) -> Option<f64> {
+        self.break_item().map(|(item, _)| {
+            let item = &self.items[item];
+            item.profit as f64 / item.weight as f64
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromStr for KnapsackInput {
+    type Err = KnapsackInputError;
+
+    /// Parse the input from a string, using the same format as `KnapsackInput::parse_input`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        KnapsackInput::parse_input(s.as_bytes())
+    }
+}
+
+impl<'a> IntoIterator for &'a KnapsackInput {
+    type Item = &'a KnapsackItem;
+    type IntoIter = core::slice::Iter<'a, KnapsackItem>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+impl IntoIterator for KnapsackInput {
+    type Item = KnapsackItem;
+    type IntoIter = alloc::vec::IntoIter<KnapsackItem>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
     }
 }
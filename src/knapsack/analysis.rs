@@ -0,0 +1,76 @@
+use serde::Serialize;
+
+use super::KnapsackInput;
+
+/// Summary statistics of a problem instance, useful for choosing a solver
+#[derive(Debug, Clone, Serialize)]
+pub struct InstanceStats {
+    pub item_count: usize,
+    pub capacity: u64,
+    pub min_weight: u64,
+    pub max_weight: u64,
+    pub mean_weight: f64,
+    pub min_profit: u64,
+    pub max_profit: u64,
+    pub mean_profit: f64,
+    /// Pearson correlation coefficient between the weights and the profits of the items
+    /// (0 if either of them is constant)
+    pub correlation: f64,
+    /// Ratio between the capacity and the total weight of the items
+    pub capacity_ratio: f64,
+    /// Number of items dominated by another item (see `KnapsackInput::count_dominated`)
+    pub dominated_count: usize,
+}
+
+impl KnapsackInput {
+    /// Compute summary statistics of the instance
+    ///
+    /// Strongly correlated instances (correlation close to 1) are usually the hardest ones for the
+    /// core based algorithms such as MinKnap.
+    pub fn analyze(&self) -> InstanceStats {
+        let n = self.items.len();
+        let weights = self.items.iter().map(|item| item.weight);
+        let profits = self.items.iter().map(|item| item.profit);
+
+        let total_weight: u64 = weights.clone().sum();
+        let total_profit: u64 = profits.clone().sum();
+        let (mean_weight, mean_profit) = match n {
+            0 => (0.0, 0.0),
+            _ => (
+                total_weight as f64 / n as f64,
+                total_profit as f64 / n as f64,
+            ),
+        };
+
+        let (covariance, weight_variance, profit_variance) =
+            self.items
+                .iter()
+                .fold((0.0, 0.0, 0.0), |(cov, var_w, var_p), item| {
+                    let dw = item.weight as f64 - mean_weight;
+                    let dp = item.profit as f64 - mean_profit;
+                    (cov + dw * dp, var_w + dw * dw, var_p + dp * dp)
+                });
+        let correlation = if weight_variance > 0.0 && profit_variance > 0.0 {
+            covariance / (weight_variance * profit_variance).sqrt()
+        } else {
+            0.0
+        };
+
+        InstanceStats {
+            item_count: n,
+            capacity: self.capacity,
+            min_weight: weights.clone().min().unwrap_or(0),
+            max_weight: weights.max().unwrap_or(0),
+            mean_weight,
+            min_profit: profits.clone().min().unwrap_or(0),
+            max_profit: profits.max().unwrap_or(0),
+            mean_profit,
+            correlation,
+            capacity_ratio: match total_weight {
+                0 => f64::INFINITY,
+                _ => self.capacity as f64 / total_weight as f64,
+            },
+            dominated_count: self.count_dominated(),
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{
+    minknap::{efficiency_cmp, prepare_items, scaled_profit, BreakSolution},
+    KnapsackInput, KnapsackItem, KnapsackMethod, KnapsackSolution, KnapsackSolveError,
+    KnapsackSolver, SolverCapabilities,
+};
+
+/// Marks the first operation of a chain in the operation history
+const NO_PARENT: usize = usize::MAX;
+
+/// Exact solver using Pisinger's balanced operations, starting from the break solution of the
+/// efficiency ordering (as `MinKnapSolver` does).
+///
+/// A balanced filling is obtained from the break solution by only adding items while the weight
+/// doesn't exceed the capacity, and only removing items while it does. Every optimal solution is a
+/// balanced filling, so only these states are enumerated. Since each state keeps the position of
+/// its last removed item, the items don't have to be considered in a fixed alternating order as in
+/// `MinKnapSolver`, and all the kept states have a weight in (capacity - max weight, capacity].
+/// This generally reduces the number of states on hard (strongly correlated) instances.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BalKnapSolver;
+
+#[derive(Clone, Copy)]
+struct BalState {
+    weight: u64,
+    profit: u64,
+    /// The items before this position (in the efficiency ordering) can still be removed
+    s: usize,
+    /// The index of the last operation that led to this state in the history
+    op: usize,
+}
+
+#[derive(Clone, Copy)]
+struct Operation {
+    /// The position (in the efficiency ordering) of the added or removed item
+    item: usize,
+    parent: usize,
+}
+
+struct BalKnapInstance<'a> {
+    problem_instance: &'a KnapsackInput,
+    /// A vector containing the indices of the items sorted by efficiency
+    efficiency_ordering: Vec<usize>,
+    /// The items before this position are included in the break solution
+    break_index: usize,
+    /// The best feasible profit found so far
+    profit_lower_bound: u64,
+    /// The last operation of the best solution found so far
+    best_sol_op: usize,
+    /// Every operation that led to a kept state, linked to the previous operation of the state
+    history: Vec<Operation>,
+}
+
+impl BalKnapInstance<'_> {
+    /// Returns the item at the given efficiency order index
+    fn get_item(&self, efficiency_order_idx: usize) -> KnapsackItem {
+        self.problem_instance.items[self.efficiency_ordering[efficiency_order_idx]]
+    }
+
+    /// Returns the upper bound of the profit that can be reached from the given state, where `t`
+    /// is the next item that can be added, or None if the state can't become feasible
+    fn get_profit_upper_bound(&self, state: &BalState, t: usize) -> Option<u64> {
+        let capacity = self.problem_instance.capacity;
+
+        if state.weight <= capacity {
+            // Only the items from t onwards can be added, none of them being more efficient than t
+            if t >= self.efficiency_ordering.len() {
+                Some(state.profit)
+            } else {
+                let next_item = self.get_item(t);
+                Some(
+                    state
+                        .profit
+                        .saturating_add(scaled_profit(capacity - state.weight, &next_item)),
+                )
+            }
+        } else if state.s == 0 {
+            // Over capacity, without any item left to remove
+            None
+        } else {
+            // The excess weight has to be removed, none of the items being less efficient than s-1
+            let prev_item = self.get_item(state.s - 1);
+            Some(
+                state
+                    .profit
+                    .saturating_sub(scaled_profit(state.weight - capacity, &prev_item)),
+            )
+        }
+    }
+
+    /// Apply the operation on the item at the given efficiency order index to the state (adding
+    /// the items after the break index, removing the other ones), where `t` is the next item that
+    /// can be added afterwards
+    ///
+    /// Returns the new state, unless it can't improve the best solution found so far
+    fn branch(&mut self, state: &BalState, item_idx: usize, t: usize) -> Option<BalState> {
+        let item = self.get_item(item_idx);
+        let new_state = if item_idx >= self.break_index {
+            BalState {
+                weight: state.weight + item.weight,
+                profit: state.profit + item.profit,
+                ..*state
+            }
+        } else {
+            BalState {
+                weight: state.weight - item.weight,
+                profit: state.profit - item.profit,
+                s: item_idx,
+                ..*state
+            }
+        };
+
+        let upper_bound = self.get_profit_upper_bound(&new_state, t)?;
+        let improves = new_state.weight <= self.problem_instance.capacity
+            && new_state.profit > self.profit_lower_bound;
+        if upper_bound <= self.profit_lower_bound && !improves {
+            return None;
+        }
+
+        self.history.push(Operation {
+            item: item_idx,
+            parent: state.op,
+        });
+        let new_state = BalState {
+            op: self.history.len() - 1,
+            ..new_state
+        };
+
+        if improves {
+            self.profit_lower_bound = new_state.profit;
+            self.best_sol_op = new_state.op;
+        }
+
+        Some(new_state)
+    }
+
+    /// Discard the dominated states, where a state is dominated by another one that has a lower or
+    /// equal weight, a higher or equal profit and at least as many items left to remove
+    ///
+    /// The positions of the states are the last sort key, so that among equivalent states the
+    /// first created one is always kept.
+    fn remove_dominated(states: &mut Vec<BalState>) {
+        let mut order: Vec<usize> = (0..states.len()).collect();
+        order.sort_unstable_by(|&i, &j| {
+            let (a, b) = (&states[i], &states[j]);
+            b.s.cmp(&a.s)
+                .then(a.weight.cmp(&b.weight))
+                .then(b.profit.cmp(&a.profit))
+                .then(i.cmp(&j))
+        });
+        *states = order.into_iter().map(|i| states[i]).collect();
+
+        // The (weight, profit) pairs of the kept states, ordered by weight, with increasing profits
+        // (the states are traversed in decreasing order of s, so all of them have a higher or equal s)
+        let mut staircase: Vec<(u64, u64)> = Vec::new();
+
+        states.retain(|state| {
+            let pos = staircase.partition_point(|&(weight, _)| weight <= state.weight);
+            if pos > 0 && staircase[pos - 1].1 >= state.profit {
+                return false;
+            }
+
+            let dominated_end =
+                pos + staircase[pos..].partition_point(|&(_, profit)| profit <= state.profit);
+            staircase.drain(pos..dominated_end);
+            staircase.insert(pos, (state.weight, state.profit));
+            true
+        });
+    }
+
+    /// Solve the problem, returning the best profit found and the items whose decision differs
+    /// from the break solution, along with their new decision
+    fn solve(mut self, break_solution: &BreakSolution) -> (u64, Vec<(usize, bool)>) {
+        let n = self.efficiency_ordering.len();
+        let capacity = self.problem_instance.capacity;
+
+        // For every (weight, profit) pair reached over the capacity, the position below which all
+        // the items have already been removed from it. When an identical state is reached again
+        // with a higher s, only the items between this position and its s have to be removed
+        let mut removed_below: BTreeMap<(u64, u64), usize> = BTreeMap::new();
+
+        let mut states = vec![BalState {
+            weight: break_solution.total_weight,
+            profit: break_solution.total_profit,
+            s: self.break_index,
+            op: NO_PARENT,
+        }];
+
+        for t in self.break_index..n {
+            let mut next_states = Vec::with_capacity(states.len() * 2);
+            let mut over_capacity = Vec::new();
+
+            // All the states are under the capacity, so the item t can be added to any of them
+            for state in &states {
+                if self
+                    .get_profit_upper_bound(state, t + 1)
+                    .is_some_and(|bound| bound > self.profit_lower_bound)
+                {
+                    next_states.push(*state);
+                }
+
+                if let Some(new_state) = self.branch(state, t, t + 1) {
+                    if new_state.weight > capacity {
+                        over_capacity.push(new_state);
+                    } else {
+                        next_states.push(new_state);
+                    }
+                }
+            }
+
+            // Remove items from the states over the capacity until they fit again, before adding
+            // the next item
+            while !over_capacity.is_empty() {
+                BalKnapInstance::remove_dominated(&mut over_capacity);
+
+                let mut still_over_capacity = Vec::new();
+                for state in &over_capacity {
+                    let removed = removed_below
+                        .entry((state.weight, state.profit))
+                        .or_insert(0);
+                    let first_item = *removed;
+                    *removed = first_item.max(state.s);
+
+                    for item_idx in (first_item..state.s).rev() {
+                        if let Some(new_state) = self.branch(state, item_idx, t + 1) {
+                            if new_state.weight > capacity {
+                                still_over_capacity.push(new_state);
+                            } else {
+                                next_states.push(new_state);
+                            }
+                        }
+                    }
+                }
+                over_capacity = still_over_capacity;
+            }
+
+            // The lower bound may have been improved after some states were kept
+            next_states.retain(|state| {
+                self.get_profit_upper_bound(state, t + 1)
+                    .is_some_and(|bound| bound > self.profit_lower_bound)
+            });
+            BalKnapInstance::remove_dominated(&mut next_states);
+            states = next_states;
+
+            if states.is_empty() {
+                break;
+            }
+        }
+
+        let mut changed_items = Vec::new();
+        let mut op = self.best_sol_op;
+        while op != NO_PARENT {
+            let item_idx = self.history[op].item;
+            // The items before the break index were removed, the other ones were added
+            changed_items.push((
+                self.efficiency_ordering[item_idx],
+                item_idx >= self.break_index,
+            ));
+            op = self.history[op].parent;
+        }
+
+        (self.profit_lower_bound, changed_items)
+    }
+}
+
+impl KnapsackSolver for BalKnapSolver {
+    fn solve(&self, input: &KnapsackInput) -> KnapsackSolution {
+        let (efficiency_ordering, mut decision_vec, base_profit) =
+            prepare_items(input, &efficiency_cmp);
+        let break_solution =
+            BreakSolution::new(input, &efficiency_ordering, decision_vec.as_mut_bitslice());
+
+        let instance = BalKnapInstance {
+            problem_instance: input,
+            efficiency_ordering,
+            break_index: break_solution.break_index,
+            profit_lower_bound: break_solution.total_profit,
+            best_sol_op: NO_PARENT,
+            history: Vec::new(),
+        };
+        let (profit, changed_items) = instance.solve(&break_solution);
+
+        for (item, decision) in changed_items {
+            decision_vec.set(item, decision);
+        }
+
+        KnapsackSolution {
+            items: decision_vec.iter_ones().collect(),
+            total_value: profit + base_profit,
+        }
+    }
+
+    fn try_solve(&self, input: &KnapsackInput) -> Result<KnapsackSolution, KnapsackSolveError> {
+        input.check_totals()?;
+        Ok(self.solve(input))
+    }
+
+    fn method(&self) -> KnapsackMethod {
+        KnapsackMethod::BalKnap
+    }
+
+    fn capabilities(&self) -> SolverCapabilities {
+        SolverCapabilities::EXACT
+            | SolverCapabilities::ZERO_WEIGHT_ITEMS
+            | SolverCapabilities::SCALABLE
+    }
+}
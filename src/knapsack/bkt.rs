@@ -1,9 +1,24 @@
-use super::{KnapsackInput, KnapsackMethod, KnapsackSolution, KnapsackSolver};
+use alloc::vec::Vec;
 
+use super::{
+    minknap::{efficiency_cmp, scaled_profit},
+    KnapsackInput, KnapsackMethod, KnapsackSolution, KnapsackSolveError, KnapsackSolver,
+    SolveMetrics, SolverCapabilities,
+};
+
+#[derive(Debug, Clone, Copy, Default)]
 pub struct BktSolver;
 
 impl BktSolver {
-    fn bkt_iterative(input: &KnapsackInput) -> KnapsackSolution {
+    /// Solve the problem, returning the number of stack pushes and pops alongside the solution
+    #[cfg(feature = "metrics")]
+    pub fn solve_with_metrics(&self, input: &KnapsackInput) -> (KnapsackSolution, SolveMetrics) {
+        let mut metrics = SolveMetrics::default();
+        let solution = BktSolver::bkt_iterative(input, &mut metrics);
+        (solution, metrics)
+    }
+
+    fn bkt_iterative(input: &KnapsackInput, metrics: &mut SolveMetrics) -> KnapsackSolution {
         let n = input.items.len();
         let mut best_solution = KnapsackSolution {
             items: Vec::new(),
@@ -24,6 +39,7 @@ impl BktSolver {
                 // Explore the possibility of including the current item
                 if current_weight + item.weight <= input.capacity {
                     stack.push((current_item, current_weight, current_value, true));
+                    metrics.record_push();
                     current_solution.items.push(current_item);
                     current_solution.total_value += item.profit;
                     current_weight += item.weight;
@@ -33,6 +49,7 @@ impl BktSolver {
                 }
                 // Explore the possibility of not including the current item
                 stack.push((current_item, current_weight, current_value, false));
+                metrics.record_push();
                 current_item += 1;
                 continue;
             }
@@ -42,6 +59,7 @@ impl BktSolver {
             }
 
             if let Some((item, weight, value, included)) = stack.pop() {
+                metrics.record_pop();
                 if included {
                     current_solution.items.pop();
                     current_solution.total_value -= input.items[item].profit;
@@ -56,14 +74,89 @@ impl BktSolver {
 
         best_solution
     }
+
+    /// Searches for a solution with a value of at least `threshold`, with the items (all of them
+    /// with a positive weight) sorted by efficiency, stopping as soon as one is found
+    ///
+    /// A branch is pruned when even its LP bound (the remaining capacity filled greedily, the last
+    /// item being taken fractionally) can't reach the threshold.
+    fn reaches_threshold(
+        input: &KnapsackInput,
+        ordering: &[usize],
+        pos: usize,
+        weight: u64,
+        value: u64,
+        threshold: u64,
+    ) -> bool {
+        if value >= threshold {
+            return true;
+        }
+        if pos == ordering.len() {
+            return false;
+        }
+
+        let mut bound = value;
+        let mut remaining_capacity = input.capacity - weight;
+        for &i in &ordering[pos..] {
+            let item = &input.items[i];
+            if item.weight <= remaining_capacity {
+                remaining_capacity -= item.weight;
+                bound += item.profit;
+            } else {
+                bound += scaled_profit(remaining_capacity, item);
+                break;
+            }
+        }
+        if bound < threshold {
+            log_trace!(
+                "pruned at item {}: bound {} below the threshold {}",
+                pos,
+                bound,
+                threshold
+            );
+            return false;
+        }
+
+        let item = &input.items[ordering[pos]];
+        (weight + item.weight <= input.capacity
+            && BktSolver::reaches_threshold(
+                input,
+                ordering,
+                pos + 1,
+                weight + item.weight,
+                value + item.profit,
+                threshold,
+            ))
+            || BktSolver::reaches_threshold(input, ordering, pos + 1, weight, value, threshold)
+    }
 }
 
 impl KnapsackSolver for BktSolver {
     fn solve(&self, input: &KnapsackInput) -> KnapsackSolution {
-        BktSolver::bkt_iterative(input)
+        BktSolver::bkt_iterative(input, &mut SolveMetrics::default())
+    }
+
+    fn try_solve(&self, input: &KnapsackInput) -> Result<KnapsackSolution, KnapsackSolveError> {
+        input.check_totals()?;
+        Ok(self.solve(input))
+    }
+
+    /// The zero weight items are always taken, so that only the items with a positive weight
+    /// (for which the efficiency ordering is a total order) are searched
+    fn has_solution_at_least(&self, input: &KnapsackInput, threshold: u64) -> bool {
+        let (zero_weight, mut ordering): (Vec<usize>, Vec<usize>) =
+            (0..input.items.len()).partition(|&i| input.items[i].weight == 0);
+        ordering.sort_by(|&a, &b| efficiency_cmp(&input.items[a], &input.items[b]));
+        let base_value = zero_weight.iter().map(|&i| input.items[i].profit).sum();
+
+        BktSolver::reaches_threshold(input, &ordering, 0, 0, base_value, threshold)
     }
 
     fn method(&self) -> super::KnapsackMethod {
         KnapsackMethod::Bkt
     }
+
+    fn capabilities(&self) -> SolverCapabilities {
+        SolverCapabilities::EXACT | SolverCapabilities::ZERO_WEIGHT_ITEMS
+    }
 }
@@ -0,0 +1,16 @@
+use super::{KnapsackInput, KnapsackSolution, KnapsackSolver};
+
+/// Solve the problem on the blocking thread pool of tokio, so that the CPU bound work doesn't
+/// block the async runtime
+///
+/// The solver and the input are moved to the blocking thread. Fails if the solver panics (or if
+/// the runtime is shutting down).
+pub async fn solve_async<S>(
+    solver: S,
+    input: KnapsackInput,
+) -> Result<KnapsackSolution, tokio::task::JoinError>
+where
+    S: KnapsackSolver + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || solver.solve(&input)).await
+}
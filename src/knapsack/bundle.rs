@@ -0,0 +1,125 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{KnapsackInput, KnapsackInputError, KnapsackItem, KnapsackSolution, KnapsackSolver};
+
+/// A problem in which some groups of items must be selected together or not at all
+///
+/// Each group is merged into a single composite item, with the total weight and profit of its
+/// members, and the solutions are expanded back to the original items.
+#[derive(Debug, Clone)]
+pub struct BundledInput {
+    // the problem on the composite items
+    bundled_input: KnapsackInput,
+    // original indices of the members of each composite item
+    members: Vec<Vec<usize>>,
+}
+
+/// Returns the representative of the set of the item in the union-find forest
+fn find(parent: &mut [usize], mut item: usize) -> usize {
+    while parent[item] != item {
+        parent[item] = parent[parent[item]];
+        item = parent[item];
+    }
+    item
+}
+
+/// Groups the items coupled by the bundles, the bundles sharing an item being merged
+///
+/// Every item belongs to exactly one group (the items outside of any bundle are alone in theirs),
+/// and the groups are ordered by their lowest member. Fails if an index is out of bounds.
+pub(super) fn bundle_members(
+    n: usize,
+    bundles: &[Vec<usize>],
+) -> Result<Vec<Vec<usize>>, KnapsackInputError> {
+    if let Some(&index) = bundles.iter().flatten().find(|&&index| index >= n) {
+        return Err(KnapsackInputError::InvalidBundleItem { index });
+    }
+
+    // Union-find over the items, linking the members of each bundle to its first item
+    let mut parent: Vec<usize> = (0..n).collect();
+    for bundle in bundles {
+        if let Some((&first, rest)) = bundle.split_first() {
+            for &item in rest {
+                let (a, b) = (find(&mut parent, first), find(&mut parent, item));
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    let mut group_of = vec![usize::MAX; n];
+    let mut members: Vec<Vec<usize>> = Vec::new();
+    for item in 0..n {
+        let root = find(&mut parent, item);
+        if group_of[root] == usize::MAX {
+            group_of[root] = members.len();
+            members.push(Vec::new());
+        }
+        members[group_of[root]].push(item);
+    }
+
+    Ok(members)
+}
+
+impl KnapsackInput {
+    /// Require the items of each bundle to be selected together
+    ///
+    /// The bundles sharing an item are merged, since all their items are then coupled. The items
+    /// outside of any bundle are kept as they are. Fails if an index is out of bounds, or if the
+    /// total weight or profit of a bundle overflows.
+    pub fn with_bundles(&self, bundles: &[Vec<usize>]) -> Result<BundledInput, KnapsackInputError> {
+        // The composite items are ordered by their lowest member
+        let members = bundle_members(self.items.len(), bundles)?;
+
+        let items = members
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .try_fold(KnapsackItem::new(0, 0), |total, &i| {
+                        Some(KnapsackItem::new(
+                            total.weight.checked_add(self.items[i].weight)?,
+                            total.profit.checked_add(self.items[i].profit)?,
+                        ))
+                    })
+                    .ok_or(KnapsackInputError::BundleOverflow { index: group[0] })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(BundledInput {
+            bundled_input: KnapsackInput {
+                items,
+                labels: Vec::new(),
+                ..*self
+            },
+            members,
+        })
+    }
+}
+
+impl BundledInput {
+    /// Solve the problem on the composite items and expand the solution to the original items
+    pub fn solve(&self, solver: &dyn KnapsackSolver) -> KnapsackSolution {
+        if self.bundled_input.items.is_empty() {
+            return KnapsackSolution::default();
+        }
+        let bundled_solution = solver.solve(&self.bundled_input);
+
+        let mut items: Vec<usize> = bundled_solution
+            .items
+            .iter()
+            .flat_map(|&composite| self.members[composite].iter().copied())
+            .collect();
+        items.sort_unstable();
+
+        KnapsackSolution {
+            items,
+            total_value: bundled_solution.total_value,
+        }
+    }
+
+    /// The original indices of the items merged into each composite item
+    pub fn members(&self) -> &[Vec<usize>] {
+        &self.members
+    }
+}
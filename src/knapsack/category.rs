@@ -0,0 +1,102 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use super::{KnapsackInput, KnapsackInputError, KnapsackItem, KnapsackSolution, KnapsackSolver};
+
+/// Highest integer up to which every integer is exactly representable as an `f64`
+const MAX_EXACT_FLOAT_INTEGER: u64 = 1 << 53;
+
+/// A problem in which the profits of the items are multiplied by the weight of their category
+///
+/// The solvers are given the effective (scaled) profits, so the categories with higher weights are
+/// preferred, but the value of the solutions is computed with the original profits.
+#[derive(Debug, Clone)]
+pub struct CategorizedInput {
+    // the problem with the effective profits
+    weighted_input: KnapsackInput,
+    // the original profit of each item
+    profits: Vec<u64>,
+}
+
+impl KnapsackInput {
+    /// Scale the profit of each item by the weight of its category before solving
+    ///
+    /// There must be one category per item. The categories missing from `category_weights` have
+    /// a weight of 1, and the effective profits are rounded to the nearest integer. The profits
+    /// are scaled as `f64` values, which are exact only up to 2^53, so the items of a weighted
+    /// category must have a profit and an effective profit of at most 2^53 (the items with a
+    /// weight of 1 keep their profit as it is).
+    ///
+    /// Fails if the number of categories doesn't match the number of items, if a weight is
+    /// negative or not finite, or if a profit of a weighted item exceeds 2^53.
+    pub fn with_category_weights(
+        &self,
+        categories: &[u32],
+        category_weights: &BTreeMap<u32, f64>,
+    ) -> Result<CategorizedInput, KnapsackInputError> {
+        if categories.len() != self.items.len() {
+            return Err(KnapsackInputError::CategoryCountMismatch);
+        }
+        if let Some((&category, _)) = category_weights
+            .iter()
+            .find(|(_, &weight)| !weight.is_finite() || weight < 0.0)
+        {
+            return Err(KnapsackInputError::InvalidCategoryWeight { category });
+        }
+
+        let items = self
+            .items
+            .iter()
+            .zip(categories)
+            .enumerate()
+            .map(|(index, (item, category))| {
+                let weight = category_weights.get(category).copied().unwrap_or(1.0);
+                if weight == 1.0 {
+                    return Ok(*item);
+                }
+                // The scaled profit is never negative, so adding 0.5 before truncating rounds it
+                // to the nearest integer (`f64::round` is not available without std)
+                let profit = item.profit as f64 * weight + 0.5;
+                if item.profit > MAX_EXACT_FLOAT_INTEGER || profit > MAX_EXACT_FLOAT_INTEGER as f64
+                {
+                    return Err(KnapsackInputError::InexactWeightedProfit { index });
+                }
+                Ok(KnapsackItem {
+                    profit: profit as u64,
+                    ..*item
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(CategorizedInput {
+            weighted_input: KnapsackInput {
+                items,
+                labels: self.labels.clone(),
+                ..*self
+            },
+            profits: self.items.iter().map(|item| item.profit).collect(),
+        })
+    }
+}
+
+impl CategorizedInput {
+    /// Solve the problem with the effective profits, reporting the original value of the solution
+    pub fn solve(&self, solver: &dyn KnapsackSolver) -> KnapsackSolution {
+        let weighted_solution = solver.solve(&self.weighted_input);
+        let total_value = weighted_solution
+            .items
+            .iter()
+            .map(|&item| self.profits[item])
+            .sum();
+
+        KnapsackSolution {
+            items: weighted_solution.items,
+            total_value,
+        }
+    }
+
+    /// The problem with the effective profits, which is given to the solvers
+    pub fn weighted_input(&self) -> &KnapsackInput {
+        &self.weighted_input
+    }
+}
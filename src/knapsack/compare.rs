@@ -0,0 +1,74 @@
+use alloc::vec::Vec;
+
+use super::{KnapsackInput, KnapsackSolution, KnapsackSolver, SolutionError, SolverCapabilities};
+
+/// The solution of one of the compared solvers
+#[derive(Debug, Clone)]
+pub struct SolverOutcome {
+    pub name: &'static str,
+    /// Whether the solver claims to always return an optimal solution
+    pub exact: bool,
+    pub solution: KnapsackSolution,
+    /// Why the solution is invalid for the input, if it is
+    pub error: Option<SolutionError>,
+}
+
+/// The solutions of several solvers for the same input (see `compare_solvers`)
+#[derive(Debug, Clone)]
+pub struct ComparisonReport {
+    /// The outcome of each solver, in the order they were given
+    pub outcomes: Vec<SolverOutcome>,
+    /// The best value among the valid solutions, if any
+    pub best_value: Option<u64>,
+}
+
+impl SolverOutcome {
+    fn agrees_with(&self, best_value: Option<u64>) -> bool {
+        self.error.is_none() && (!self.exact || Some(self.solution.total_value) == best_value)
+    }
+}
+
+impl ComparisonReport {
+    /// The solvers that returned an invalid solution, and the exact solvers that missed the best
+    /// value
+    pub fn disagreeing(&self) -> impl Iterator<Item = &SolverOutcome> {
+        self.outcomes
+            .iter()
+            .filter(|outcome| !outcome.agrees_with(self.best_value))
+    }
+
+    /// Checks that every solution is valid and that all the exact solvers found the best value
+    pub fn all_agree(&self) -> bool {
+        self.disagreeing().next().is_none()
+    }
+}
+
+/// Solve the input with each solver and compare the solutions
+///
+/// Every solution is verified against the input. Since an approximate solver may find the optimum
+/// too, the best value is taken over all the valid solutions, so an exact solver returning less
+/// (or an invalid solution) is reported as disagreeing.
+pub fn compare_solvers(solvers: &[&dyn KnapsackSolver], input: &KnapsackInput) -> ComparisonReport {
+    let outcomes: Vec<SolverOutcome> = solvers
+        .iter()
+        .map(|solver| {
+            let solution = solver.solve(input);
+            SolverOutcome {
+                name: solver.name(),
+                exact: solver.capabilities().contains(SolverCapabilities::EXACT),
+                error: solution.verify(input).err(),
+                solution,
+            }
+        })
+        .collect();
+    let best_value = outcomes
+        .iter()
+        .filter(|outcome| outcome.error.is_none())
+        .map(|outcome| outcome.solution.total_value)
+        .max();
+
+    ComparisonReport {
+        outcomes,
+        best_value,
+    }
+}
@@ -0,0 +1,98 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::KnapsackInput;
+
+impl KnapsackInput {
+    /// Returns the number of items dominated by another item (one with a lower or equal weight
+    /// and a higher or equal profit, the first of several identical items not being dominated)
+    ///
+    /// A high count suggests that `remove_dominated` can shrink the instance a lot, although it
+    /// only removes the dominated items that don't fit together with their dominators.
+    pub fn count_dominated(&self) -> usize {
+        let mut dominated = 0;
+        self.scan_dominators(|_, dominators, _| {
+            if dominators > 0 {
+                dominated += 1;
+            }
+        });
+
+        dominated
+    }
+
+    /// Remove the items that are never needed for reaching the optimum, because of the items
+    /// dominating them (with a lower or equal weight and a higher or equal profit)
+    ///
+    /// An item can't simply be dropped as soon as another item dominates it, since the optimum may
+    /// contain both of them. However, if an optimal solution contains the item but not one of its
+    /// dominators, they can be swapped. Thus, an item is only removed when it doesn't fit in the
+    /// knapsack together with all its dominators (which includes the items heavier than the
+    /// capacity). Among identical items, the ones with lower indices dominate the other ones.
+    ///
+    /// Returns the reduced instance and the original index of each of its items, for mapping the
+    /// solutions back to the original items.
+    pub fn remove_dominated(&self) -> (KnapsackInput, Vec<usize>) {
+        let n = self.items.len();
+        let mut kept = vec![true; n];
+        self.scan_dominators(|i, _, dominators_weight| {
+            kept[i] = dominators_weight.saturating_add(self.items[i].weight) <= self.capacity;
+        });
+
+        let kept_items: Vec<usize> = (0..n).filter(|&i| kept[i]).collect();
+        let reduced_input = KnapsackInput {
+            items: kept_items.iter().map(|&i| self.items[i]).collect(),
+            labels: self.select_labels(&kept_items),
+            ..*self
+        };
+
+        (reduced_input, kept_items)
+    }
+
+    /// Calls `visit` for every item with the number and the (saturated) total weight of its
+    /// dominators, the items with a lower or equal weight and a higher or equal profit, where the
+    /// lower indices dominate among identical items
+    fn scan_dominators(&self, mut visit: impl FnMut(usize, usize, u64)) {
+        // With the items sorted by weight (and by decreasing profit for equal weights), the
+        // dominators of an item are the items before it with a higher or equal profit
+        let mut order: Vec<usize> = (0..self.items.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (item_a, item_b) = (&self.items[a], &self.items[b]);
+            item_a
+                .weight
+                .cmp(&item_b.weight)
+                .then(item_b.profit.cmp(&item_a.profit))
+                .then(a.cmp(&b))
+        });
+
+        // The distinct profits in descending order, so that the dominators of an item are the
+        // ones whose profit rank is lower or equal to its rank
+        let mut profits: Vec<u64> = self.items.iter().map(|item| item.profit).collect();
+        profits.sort_unstable_by(|a, b| b.cmp(a));
+        profits.dedup();
+
+        // Fenwick tree holding the number and the total weight of the items seen so far, indexed
+        // by profit rank
+        let mut dominators = vec![(0usize, 0u64); profits.len() + 1];
+
+        for &i in &order {
+            let item = &self.items[i];
+            let rank = profits.partition_point(|&profit| profit > item.profit) + 1;
+
+            let (mut count, mut weight) = (0, 0u64);
+            let mut pos = rank;
+            while pos > 0 {
+                count += dominators[pos].0;
+                weight = weight.saturating_add(dominators[pos].1);
+                pos &= pos - 1;
+            }
+            visit(i, count, weight);
+
+            let mut pos = rank;
+            while pos < dominators.len() {
+                dominators[pos].0 += 1;
+                dominators[pos].1 = dominators[pos].1.saturating_add(item.weight);
+                pos += pos & pos.wrapping_neg();
+            }
+        }
+    }
+}
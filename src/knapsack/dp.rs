@@ -1,44 +1,185 @@
-use super::{KnapsackInput, KnapsackMethod, KnapsackSolution, KnapsackSolver};
+use alloc::vec;
+use alloc::vec::Vec;
 
+use super::{
+    view::{ItemSource, KnapsackView},
+    KnapsackInput, KnapsackItem, KnapsackMethod, KnapsackSolution, KnapsackSolveError,
+    KnapsackSolver, SolveMetrics, SolverCapabilities,
+};
+
+use bitvec::prelude::*;
 use ndarray::Array2;
 
+/// Maximum number of cells of a table that can be addressed (its size in bytes must fit in an isize)
+pub(super) const MAX_ADDRESSABLE_CELLS: u64 =
+    isize::MAX as u64 / core::mem::size_of::<u64>() as u64;
+
+/// Panic message of the infallible solves when the profit axis of the table can't be indexed
+const TOTAL_PROFIT_OVERFLOW: &str = "the total profit of the items overflows a u64";
+
+/// Callback reporting the progress of a DP solve, called with the number of rows (items) done so
+/// far and the total number of rows
+pub type ProgressCallback<'c> = &'c dyn Fn(usize, usize);
+
+/// Secondary objective used for choosing among the solutions with the optimal value
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SolveObjective {
+    /// The solution with the minimum total weight (the choice of `DpSolver::solve`)
+    #[default]
+    MinWeight,
+    /// The solution with the fewest items among the ones with the minimum total weight
+    MinItems,
+}
+
+/// Exact solver based on a profit indexed dynamic programming table.
+///
+/// Among all the optimal solutions, the returned one always has the minimum total weight, and
+/// among those, lower index items are preferred (see `gen_path` for the exact rule).
+#[derive(Debug, Clone, Copy, Default)]
 pub struct DpSolver;
 
+/// A filled profit indexed DP table of an input.
+///
+/// The table doesn't depend on the capacity of the input, so it can be built once and queried for
+/// the best solution of any capacity (e.g. for a sensitivity analysis of the capacity).
+pub struct DpTable<'a> {
+    input: &'a KnapsackInput,
+    dp_table: Array2<u64>,
+}
+
+impl DpTable<'_> {
+    /// Returns the best solution of the input if the knapsack had the given capacity
+    ///
+    /// The last row of the table is scanned for the highest profit whose minimum weight fits in
+    /// the capacity. The items are chosen with the same rule as `DpSolver::solve`.
+    pub fn best_value_for_capacity(&self, capacity: u64) -> KnapsackSolution {
+        let last_row = self.dp_table.nrows() - 1;
+        // The unreachable profits have a weight higher than the total weight of the items
+        let total_weight = self.input.total_weight();
+        let capacity = capacity.min(total_weight);
+
+        let max_profit = self
+            .dp_table
+            .row(last_row)
+            .iter()
+            .enumerate()
+            .filter(|(_, &weight)| weight <= capacity)
+            .map(|(profit, _)| profit as u64)
+            .max()
+            .unwrap();
+
+        let mut selected_items =
+            DpSolver::gen_path(&self.dp_table, self.input, last_row, max_profit);
+        selected_items.reverse();
+
+        KnapsackSolution {
+            items: selected_items,
+            total_value: max_profit,
+        }
+    }
+}
+
 impl DpSolver {
-    fn gen_table(input: &KnapsackInput) -> Array2<u64> {
-        let n = input.items.len();
-        let max_profit = input.max_item_profit();
-        let max_cost = input.max_cost();
-        let items = &input.items;
-        let mut dp_table = Array2::<u64>::zeros((n, max_profit as usize + 1));
+    /// Fills the DP table, where dp_table[(i, profit)] is the minimum weight needed to obtain
+    /// exactly the given profit using the first i+1 items.
+    ///
+    /// If a target profit is given and it becomes reachable within the capacity, the filling stops
+    /// early, since no solution can have a higher profit. The target should be an upper bound of the
+    /// optimal profit, such as the one returned by `KnapsackInput::lp_bound`.
+    ///
+    /// If a progress callback is given, it is called after each filled row.
+    ///
+    /// Returns the table and the index of the last row that was filled.
+    fn gen_table(
+        input: &impl ItemSource,
+        target_profit: Option<u64>,
+        progress: Option<ProgressCallback>,
+    ) -> (Array2<u64>, usize) {
+        let n = input.item_count();
+        let report_progress = |rows_done| {
+            if let Some(progress) = progress {
+                progress(rows_done, n);
+            }
+        };
+        let max_profit = input.total_profit().expect(TOTAL_PROFIT_OVERFLOW);
+        let max_weight = input.max_weight().unwrap_or(0);
+        // The unreachable profits have a weight higher than the total weight of the items
+        let capacity = DpSolver::weight_limit(input);
+        // All the profits start as unreachable, except for the profit 0 (no items selected)
+        let mut dp_table =
+            Array2::<u64>::from_elem((n, max_profit as usize + 1), u64::MAX - max_weight);
+        dp_table.column_mut(0).fill(0);
+        // A zero profit first item must not replace the empty solution of profit 0
+        let first_item = input.item(0);
+        let first_profit = first_item.profit as usize;
+        dp_table[(0, first_profit)] = dp_table[(0, first_profit)].min(first_item.weight);
 
-        dp_table.row_mut(0).fill(u64::MAX - max_cost);
-        dp_table[(0, 0)] = 0;
-        dp_table[(0, items[0].profit as usize)] = items[0].weight;
+        // The highest profit that can be reached using the items considered so far. The profits
+        // above it stay unreachable, so they don't need to be filled
+        let mut reachable_profit = first_profit;
+        report_progress(1);
 
         for i in 1..n {
+            let item = input.item(i);
+            reachable_profit += item.profit as usize;
+
             // Copy the previous row elements that have a profit less than the current item's value
-            for profit in 1usize..items[i].profit as usize {
+            for profit in 1usize..item.profit as usize {
                 dp_table[(i, profit)] = dp_table[(i - 1, profit)];
             }
             // Try to improve the weight for a given profit using the current item
-            for profit in items[i].profit as usize..=max_profit as usize {
+            for profit in item.profit as usize..=reachable_profit {
                 dp_table[(i, profit)] = dp_table[(i - 1, profit)]
-                    .min(dp_table[(i - 1, profit - items[i].profit as usize)] + items[i].weight);
+                    .min(dp_table[(i - 1, profit - item.profit as usize)] + item.weight);
+            }
+            report_progress(i + 1);
+
+            // Stop early if the upper bound of the profit has been reached
+            if target_profit
+                .filter(|&target| target <= max_profit)
+                .is_some_and(|target| dp_table[(i, target as usize)] <= capacity)
+            {
+                return (dp_table, i);
             }
         }
 
-        dp_table
+        (dp_table, n - 1)
     }
 
-    fn gen_path(dp_table: &Array2<u64>, input: &KnapsackInput, max_profit: u64) -> Vec<usize> {
+    /// Reconstructs the items of a solution with the given profit and the minimum weight for it.
+    ///
+    /// The rows are traversed from the last item to the first one, and an item is selected only
+    /// if the same profit can't be reached with the same weight using the items before it. Thus,
+    /// when several item sets are optimal, the selected set is the one whose highest index is the
+    /// lowest (applied recursively), i.e. lower index items are always preferred.
+    ///
+    /// The items are returned in descending order of their indices.
+    fn gen_path(
+        dp_table: &Array2<u64>,
+        input: &impl ItemSource,
+        last_row: usize,
+        max_profit: u64,
+    ) -> Vec<usize> {
         let mut path = Vec::new();
+        DpSolver::gen_path_into(dp_table, input, last_row, max_profit, &mut path);
+        path
+    }
+
+    /// Appends the items of the solution to `path` (see `gen_path`), without allocating a new
+    /// vector
+    fn gen_path_into(
+        dp_table: &Array2<u64>,
+        input: &impl ItemSource,
+        last_row: usize,
+        max_profit: u64,
+        path: &mut Vec<usize>,
+    ) {
         let mut profit = max_profit;
 
-        for i in (1..input.items.len()).rev() {
+        for i in (1..=last_row).rev() {
             if dp_table[(i, profit as usize)] != dp_table[(i - 1, profit as usize)] {
                 path.push(i);
-                profit -= input.items[i].profit;
+                profit -= input.item(i).profit;
             }
             if profit == 0 {
                 break;
@@ -48,33 +189,814 @@ impl DpSolver {
         if profit > 0 {
             path.push(0);
         }
+    }
 
-        path
+    /// Fills the whole DP table of the input, so that it can be queried for several capacities
+    pub fn build_table<'a>(&self, input: &'a KnapsackInput) -> DpTable<'a> {
+        if input.items.is_empty() {
+            // A single row where only the profit 0 (of the empty solution) is reachable
+            return DpTable {
+                input,
+                dp_table: Array2::zeros((1, 1)),
+            };
+        }
+
+        let (dp_table, _) = DpSolver::gen_table(input, None, None);
+        DpTable { input, dp_table }
     }
-}
 
-impl KnapsackSolver for DpSolver {
-    fn solve(&self, input: &KnapsackInput) -> KnapsackSolution {
-        let dp_table = DpSolver::gen_table(input);
+    /// Returns the optimal value of the input for each of the given capacities (the capacity of
+    /// the input is ignored), filling the DP table only once
+    ///
+    /// The minimum weights of the last row are turned into suffix minimums, which are
+    /// non-decreasing in the profit, so each capacity is answered with a binary search. The values
+    /// are non-decreasing in the capacity.
+    pub fn value_curve(&self, input: &KnapsackInput, capacities: &[u64]) -> Vec<u64> {
+        if input.items.is_empty() {
+            return vec![0; capacities.len()];
+        }
+
+        let (dp_table, last_row) = DpSolver::gen_table(input, None, None);
+        // min_weights[profit] is the minimum weight needed for a profit of at least `profit`
+        let mut min_weights = dp_table.row(last_row).to_vec();
+        for profit in (0..min_weights.len() - 1).rev() {
+            min_weights[profit] = min_weights[profit].min(min_weights[profit + 1]);
+        }
+
+        // The unreachable profits have a weight higher than the total weight of the items
+        let total_weight = input.total_weight();
+
+        capacities
+            .iter()
+            .map(|&capacity| {
+                let capacity = capacity.min(total_weight);
+                // The profit 0 always fits, so at least one weight is within the capacity
+                (min_weights.partition_point(|&weight| weight <= capacity) - 1) as u64
+            })
+            .collect()
+    }
+
+    /// Returns every distinct set of items reaching the optimal profit within the capacity, with
+    /// at most `max_solutions` sets, since their number can grow exponentially.
+    ///
+    /// The item sets are enumerated by walking back the whole DP table, and the items of each set
+    /// are sorted in ascending order.
+    pub fn solve_all_optima(&self, input: &KnapsackInput, max_solutions: usize) -> Vec<Vec<usize>> {
+        if input.items.is_empty() {
+            return vec![Vec::new()];
+        }
+
+        let (dp_table, last_row) = DpSolver::gen_table(input, None, None);
+        // The unreachable profits have a weight higher than the total weight of the items
+        let total_weight = input.total_weight();
+        let capacity = input.capacity.min(total_weight);
 
         let max_profit = dp_table
-            .row(input.items.len() - 1)
+            .row(last_row)
             .iter()
             .enumerate()
-            .filter(|(_, &weight)| weight <= input.capacity)
+            .filter(|(_, &weight)| weight <= capacity)
             .map(|(profit, _)| profit as u64)
             .max()
             .unwrap();
 
-        let selected_items = DpSolver::gen_path(&dp_table, input, max_profit);
+        let mut walk = OptimaWalk {
+            dp_table: &dp_table,
+            input,
+            items: Vec::new(),
+            solutions: Vec::new(),
+            max_solutions,
+        };
+        walk.enumerate(last_row + 1, max_profit, capacity);
+        walk.solutions
+    }
 
-        KnapsackSolution {
+    /// Solves the covering problem: returns the lightest set of items whose total profit is at
+    /// least `target`, or None if even all the items together don't reach it.
+    ///
+    /// The capacity of the input is ignored. Among the lightest sets, the one with the highest
+    /// profit is returned.
+    pub fn min_weight_for_profit(
+        &self,
+        input: &KnapsackInput,
+        target: u64,
+    ) -> Option<KnapsackSolution> {
+        if input.items.is_empty() {
+            return (target == 0).then(KnapsackSolution::default);
+        }
+        if target > input.max_item_profit().expect(TOTAL_PROFIT_OVERFLOW) {
+            return None;
+        }
+
+        let (dp_table, last_row) = DpSolver::gen_table(input, None, None);
+        // The profits are traversed in descending order, so the highest one is kept on ties
+        let (profit, _) = dp_table
+            .row(last_row)
+            .iter()
+            .enumerate()
+            .skip(target as usize)
+            .rev()
+            .min_by_key(|(_, &weight)| weight)?;
+
+        let mut selected_items = DpSolver::gen_path(&dp_table, input, last_row, profit as u64);
+        selected_items.reverse();
+
+        Some(KnapsackSolution {
             items: selected_items,
-            total_value: max_profit,
+            total_value: profit as u64,
+        })
+    }
+
+    /// Returns the Pareto frontier of the problem: every non-dominated (weight, profit) pair
+    /// that can be achieved by some subset of the items, sorted by weight in ascending order.
+    ///
+    /// A pair is non-dominated if no other subset has a lower or equal weight and a higher or
+    /// equal profit. The capacity is ignored, so the frontier may contain points that exceed it.
+    pub fn pareto_frontier(&self, input: &KnapsackInput) -> Vec<(u64, u64)> {
+        if input.items.is_empty() {
+            return vec![(0, 0)];
+        }
+
+        let (dp_table, last_row) = DpSolver::gen_table(input, None, None);
+        let total_weight = input.total_weight();
+
+        let mut frontier = Vec::new();
+        let mut min_weight = u64::MAX;
+
+        // Walk the profits in descending order, keeping only the reachable profits whose
+        // minimal weight is strictly lower than the one of every higher profit
+        for (profit, &weight) in dp_table.row(last_row).iter().enumerate().rev() {
+            if weight <= total_weight && weight < min_weight {
+                min_weight = weight;
+                frontier.push((weight, profit as u64));
+            }
+        }
+
+        frontier.reverse();
+        frontier
+    }
+}
+
+/// Backtracking walk of a full DP table, enumerating the item sets with a given profit
+struct OptimaWalk<'a> {
+    dp_table: &'a Array2<u64>,
+    input: &'a KnapsackInput,
+    /// The items chosen so far, in descending order of their indices
+    items: Vec<usize>,
+    solutions: Vec<Vec<usize>>,
+    max_solutions: usize,
+}
+
+impl OptimaWalk<'_> {
+    /// Adds every set of items among the first `item_count` ones that has exactly the given profit
+    /// and a weight of at most `max_weight` to the solutions, together with the chosen items
+    fn enumerate(&mut self, item_count: usize, profit: u64, max_weight: u64) {
+        if self.solutions.len() >= self.max_solutions {
+            return;
+        }
+        if item_count == 0 {
+            if profit == 0 {
+                self.solutions
+                    .push(self.items.iter().rev().copied().collect());
+            }
+            return;
         }
+        // No set of the remaining items can reach the profit within the weight limit
+        if self.dp_table[(item_count - 1, profit as usize)] > max_weight {
+            return;
+        }
+
+        let i = item_count - 1;
+        let item = self.input.items[i];
+        if item.profit <= profit && item.weight <= max_weight {
+            self.items.push(i);
+            self.enumerate(i, profit - item.profit, max_weight - item.weight);
+            self.items.pop();
+        }
+        self.enumerate(i, profit, max_weight);
+    }
+}
+
+impl KnapsackSolver for DpSolver {
+    fn solve(&self, input: &KnapsackInput) -> KnapsackSolution {
+        DpSolver::solve_instrumented(input, &mut SolveMetrics::default(), None)
+    }
+
+    /// The table is only filled until the threshold becomes reachable within the capacity, and
+    /// not at all if it exceeds the LP bound
+    fn has_solution_at_least(&self, input: &KnapsackInput, threshold: u64) -> bool {
+        if threshold == 0 {
+            return true;
+        }
+        if input.items.is_empty() || input.lp_bound() < threshold {
+            return false;
+        }
+
+        let (dp_table, last_row) = DpSolver::gen_table(input, Some(threshold), None);
+        let capacity = DpSolver::weight_limit(input);
+        dp_table
+            .row(last_row)
+            .iter()
+            .skip(threshold as usize)
+            .any(|&weight| weight <= capacity)
+    }
+
+    fn solve_into(&self, input: &KnapsackInput, items_buf: &mut Vec<usize>) -> u64 {
+        DpSolver::solve_instrumented_into(input, &mut SolveMetrics::default(), None, items_buf)
+    }
+
+    /// The table is filled straight from the viewed slices, without copying the items
+    fn solve_view(&self, view: &KnapsackView) -> KnapsackSolution {
+        let mut items = Vec::new();
+        let total_value =
+            DpSolver::solve_instrumented_into(view, &mut SolveMetrics::default(), None, &mut items);
+
+        KnapsackSolution { items, total_value }
+    }
+
+    /// Fails if the totals of the items overflow or if the DP table can't even be addressed,
+    /// instead of panicking when allocating it
+    fn try_solve(&self, input: &KnapsackInput) -> Result<KnapsackSolution, KnapsackSolveError> {
+        input.check_totals()?;
+        self.solve_bounded(input, MAX_ADDRESSABLE_CELLS)
     }
 
     fn method(&self) -> KnapsackMethod {
         KnapsackMethod::Dp
     }
+
+    fn capabilities(&self) -> SolverCapabilities {
+        SolverCapabilities::EXACT
+            | SolverCapabilities::ZERO_WEIGHT_ITEMS
+            | SolverCapabilities::SCALABLE
+    }
+}
+
+impl DpSolver {
+    /// Solve the problem, failing instead of allocating the table if it would have more than
+    /// `max_table_cells` cells
+    ///
+    /// The table has one row per item and one column per profit level up to the total profit of
+    /// the items, so its size can be prohibitive for instances with large profits.
+    pub fn solve_bounded(
+        &self,
+        input: &KnapsackInput,
+        max_table_cells: u64,
+    ) -> Result<KnapsackSolution, KnapsackSolveError> {
+        let cells = DpSolver::table_cells(input);
+        if cells > max_table_cells as u128 {
+            return Err(KnapsackSolveError::TableTooLarge {
+                cells,
+                limit: max_table_cells,
+            });
+        }
+
+        Ok(self.solve(input))
+    }
+
+    /// Returns the number of cells of the full DP table for the given input
+    pub fn table_cells(input: &KnapsackInput) -> u128 {
+        let total_profit: u128 = input.items.iter().map(|item| item.profit as u128).sum();
+        input.items.len() as u128 * (total_profit + 1)
+    }
+
+    /// Returns the number of cells of the weight indexed table (see `solve_weight_indexed`) for
+    /// the given input
+    pub fn weight_table_cells(input: &KnapsackInput) -> u128 {
+        input.items.len() as u128 * (DpSolver::weight_limit(input) as u128 + 1)
+    }
+
+    /// Returns the highest weight a solution can have: the capacity, or the total weight of the
+    /// items if it is lower
+    fn weight_limit(input: &impl ItemSource) -> u64 {
+        let total_weight = input.total_weight();
+        input.capacity().min(total_weight)
+    }
+
+    /// Solve the problem using a weight indexed table instead of the profit indexed one, where
+    /// table[(i, weight)] is the highest profit that can be obtained with at most the given weight
+    /// using the first i+1 items.
+    ///
+    /// The table has one column per weight up to the capacity, so it is smaller than the profit
+    /// indexed one for instances with a small capacity and large profits. The returned solution
+    /// is the same as the one of `solve` (the minimum weight optimum, with the same tie rule).
+    pub fn solve_weight_indexed(&self, input: &KnapsackInput) -> KnapsackSolution {
+        let n = input.items.len();
+        if n == 0 {
+            return KnapsackSolution {
+                items: Vec::new(),
+                total_value: 0,
+            };
+        }
+
+        let items = &input.items;
+        let limit = DpSolver::weight_limit(input) as usize;
+        let mut table = Array2::<u64>::zeros((n, limit + 1));
+        for weight in (items[0].weight as usize).min(limit + 1)..=limit {
+            table[(0, weight)] = items[0].profit;
+        }
+
+        for i in 1..n {
+            let item_weight = items[i].weight as usize;
+            for weight in 0..=limit {
+                table[(i, weight)] = match weight.checked_sub(item_weight) {
+                    Some(remaining) => {
+                        table[(i - 1, weight)].max(table[(i - 1, remaining)] + items[i].profit)
+                    }
+                    None => table[(i - 1, weight)],
+                };
+            }
+        }
+
+        let total_value = table[(n - 1, limit)];
+        // The lowest weight with which the optimal profit is reached
+        let mut weight = (0..=limit)
+            .find(|&weight| table[(n - 1, weight)] == total_value)
+            .unwrap();
+
+        // An item is only selected if the profit can't be reached without it (see `gen_path`)
+        let mut selected_items = Vec::new();
+        let mut profit = total_value;
+        for i in (1..n).rev() {
+            if profit == 0 {
+                break;
+            }
+            if table[(i - 1, weight)] < profit {
+                selected_items.push(i);
+                profit -= items[i].profit;
+                weight -= items[i].weight as usize;
+            }
+        }
+        if profit > 0 {
+            selected_items.push(0);
+        }
+        selected_items.reverse();
+
+        KnapsackSolution {
+            items: selected_items,
+            total_value,
+        }
+    }
+
+    /// Solve the problem, returning the number of filled table cells alongside the solution
+    #[cfg(feature = "metrics")]
+    pub fn solve_with_metrics(&self, input: &KnapsackInput) -> (KnapsackSolution, SolveMetrics) {
+        let mut metrics = SolveMetrics::default();
+        let solution = DpSolver::solve_instrumented(input, &mut metrics, None);
+        (solution, metrics)
+    }
+
+    /// Solve the problem using O(P) memory instead of the n x P table, where P is the total profit
+    /// of the items, for instances whose table doesn't fit in memory.
+    ///
+    /// Only the last row of the table is kept while filling it, and the items are recovered with
+    /// Hirschberg's divide and conquer technique, which fills the rows of the two halves of the
+    /// items again and recurses on each half with its part of the profit. This takes
+    /// O(n * P * log(n)) time.
+    ///
+    /// The returned solution has the minimum total weight among the optimal ones, but when several
+    /// such item sets exist, it may differ from the one returned by `solve`.
+    pub fn solve_linear_space(&self, input: &KnapsackInput) -> KnapsackSolution {
+        let row = DpSolver::min_weight_row(
+            &input.items,
+            input.max_item_profit().expect(TOTAL_PROFIT_OVERFLOW) as usize,
+        );
+        let capacity = DpSolver::weight_limit(input);
+        let total_value = row.iter().rposition(|&weight| weight <= capacity).unwrap();
+
+        let mut items = Vec::new();
+        DpSolver::recover_items(&input.items, 0, total_value, &mut items);
+
+        KnapsackSolution {
+            items,
+            total_value: total_value as u64,
+        }
+    }
+
+    /// Returns the minimum weight needed to obtain exactly each profit up to `max_profit` using the
+    /// given items, u64::MAX being used for the unreachable profits
+    fn min_weight_row(items: &[KnapsackItem], max_profit: usize) -> Vec<u64> {
+        let mut row = vec![u64::MAX; max_profit + 1];
+        row[0] = 0;
+
+        for item in items {
+            let item_profit = item.profit as usize;
+            if item_profit > max_profit {
+                continue;
+            }
+            for profit in (item_profit..=max_profit).rev() {
+                row[profit] =
+                    row[profit].min(row[profit - item_profit].saturating_add(item.weight));
+            }
+        }
+
+        row
+    }
+
+    /// Adds the indices (offset by `first_index`) of a minimum weight set of the given items that
+    /// has exactly the given profit to `selected`, in ascending order
+    fn recover_items(
+        items: &[KnapsackItem],
+        first_index: usize,
+        profit: usize,
+        selected: &mut Vec<usize>,
+    ) {
+        // The empty set has the minimum weight for a zero profit
+        if profit == 0 {
+            return;
+        }
+        if items.len() == 1 {
+            selected.push(first_index);
+            return;
+        }
+
+        let mid = items.len() / 2;
+        let (left, right) = items.split_at(mid);
+        // The rows are dropped before recursing, so that only O(P) memory is used at a time
+        let left_profit = {
+            let left_row = DpSolver::min_weight_row(left, profit);
+            let right_row = DpSolver::min_weight_row(right, profit);
+            (0..=profit)
+                .min_by_key(|&left_profit| {
+                    left_row[left_profit].saturating_add(right_row[profit - left_profit])
+                })
+                .unwrap()
+        };
+
+        DpSolver::recover_items(left, first_index, left_profit, selected);
+        DpSolver::recover_items(right, first_index + mid, profit - left_profit, selected);
+    }
+
+    /// Solve the problem, using the given objective for choosing among the optimal solutions
+    pub fn solve_with_objective(
+        &self,
+        input: &KnapsackInput,
+        objective: SolveObjective,
+    ) -> KnapsackSolution {
+        match objective {
+            SolveObjective::MinWeight => self.solve(input),
+            SolveObjective::MinItems => DpSolver::solve_min_items(input),
+        }
+    }
+
+    /// Finds the optimal solution with the fewest items among the ones with the minimum total
+    /// weight.
+    ///
+    /// The table has the same profit axis as the one of `solve`, but each cell holds the item
+    /// count of its minimum weight solution as a secondary key, so that among the item sets with
+    /// the same profit and weight, the one with fewer items is kept. The zero profit items never
+    /// improve a cell, so they are left out.
+    fn solve_min_items(input: &KnapsackInput) -> KnapsackSolution {
+        let n = input.items.len();
+        if n == 0 {
+            return KnapsackSolution::default();
+        }
+
+        let max_profit = input.max_item_profit().expect(TOTAL_PROFIT_OVERFLOW) as usize;
+        let max_weight = input.max_weight().unwrap_or(0);
+        // Each cell is the (weight, item count) of a solution reaching the profit exactly, and the
+        // unreachable profits have a weight higher than the total weight of the items
+        let unreachable = (u64::MAX - max_weight, 0usize);
+        let mut dp_table = Array2::from_elem((n + 1, max_profit + 1), unreachable);
+        dp_table[(0, 0)] = (0, 0);
+
+        for i in 1..=n {
+            let item = input.items[i - 1];
+            let item_profit = item.profit as usize;
+            for profit in 0..=max_profit {
+                let (weight, count) = dp_table[(i - 1, profit)];
+                dp_table[(i, profit)] = match profit.checked_sub(item_profit) {
+                    Some(rest) if item_profit > 0 => {
+                        let (rest_weight, rest_count) = dp_table[(i - 1, rest)];
+                        (weight, count).min((rest_weight + item.weight, rest_count + 1))
+                    }
+                    _ => (weight, count),
+                };
+            }
+        }
+
+        // The optimal value is the highest profit that fits, like in `solve`
+        let capacity = DpSolver::weight_limit(input);
+        let total_value = (0..=max_profit)
+            .rev()
+            .find(|&profit| dp_table[(n, profit)].0 <= capacity)
+            .unwrap();
+
+        // An item is selected if its row improved the cell of the remaining profit
+        let mut items = Vec::new();
+        let mut profit = total_value;
+        for i in (1..=n).rev() {
+            if dp_table[(i, profit)] != dp_table[(i - 1, profit)] {
+                items.push(i - 1);
+                profit -= input.items[i - 1].profit as usize;
+            }
+        }
+        items.reverse();
+
+        KnapsackSolution {
+            items,
+            total_value: total_value as u64,
+        }
+    }
+
+    /// Solve the problem with every profit clamped to `profit_cap`, which bounds the profit axis of
+    /// the table by n * profit_cap (without a cap when `profit_cap` is None)
+    ///
+    /// The returned items are always feasible, and the value reported for them uses the original
+    /// profits. The optimal item set loses at most its excess over the cap when clamped, so the
+    /// returned value is at least OPT - the sum of max(0, profit - profit_cap) over the items of an
+    /// optimal solution. The solution is optimal when no profit exceeds the cap.
+    pub fn solve_with_profit_cap(
+        &self,
+        input: &KnapsackInput,
+        profit_cap: Option<u64>,
+    ) -> KnapsackSolution {
+        let profit_cap = match profit_cap {
+            Some(profit_cap) => profit_cap,
+            None => return self.solve(input),
+        };
+
+        let capped_input = KnapsackInput {
+            items: input
+                .items
+                .iter()
+                .map(|item| KnapsackItem {
+                    profit: item.profit.min(profit_cap),
+                    ..*item
+                })
+                .collect(),
+            labels: Vec::new(),
+            ..*input
+        };
+        let capped_solution = self.solve(&capped_input);
+
+        let total_value = capped_solution
+            .items
+            .iter()
+            .map(|&item| input.items[item].profit)
+            .sum();
+
+        KnapsackSolution {
+            items: capped_solution.items,
+            total_value,
+        }
+    }
+
+    /// Solve the problem with a soft capacity: the knapsack may be overfilled by up to
+    /// `max_overflow`, at a cost of `penalty_per_unit` for each unit of weight above the capacity
+    ///
+    /// The returned solution maximizes its profit minus the penalty, which is returned alongside
+    /// it (the value of the solution is its plain profit). Since the penalty only grows with the
+    /// weight, the best set of items for each profit is the lightest one, so the last row of the
+    /// whole table is scanned for the best penalized profit.
+    pub fn solve_with_soft_capacity(
+        &self,
+        input: &KnapsackInput,
+        penalty_per_unit: u64,
+        max_overflow: u64,
+    ) -> (KnapsackSolution, u64) {
+        if input.items.is_empty() {
+            return (KnapsackSolution::default(), 0);
+        }
+
+        let (dp_table, last_row) = DpSolver::gen_table(input, None, None);
+        // The unreachable profits have a weight higher than the total weight of the items
+        let weight_limit = input
+            .capacity
+            .saturating_add(max_overflow)
+            .min(input.total_weight());
+
+        let penalized_profit = |profit: u64, weight: u64| {
+            let penalty =
+                (weight.saturating_sub(input.capacity) as u128) * penalty_per_unit as u128;
+            profit as i128 - penalty as i128
+        };
+        let (best_profit, best_value) = dp_table
+            .row(last_row)
+            .iter()
+            .enumerate()
+            .filter(|(_, &weight)| weight <= weight_limit)
+            .map(|(profit, &weight)| (profit as u64, penalized_profit(profit as u64, weight)))
+            .fold((0, 0), |best, candidate| {
+                if candidate.1 > best.1 {
+                    candidate
+                } else {
+                    best
+                }
+            });
+
+        let mut items = DpSolver::gen_path(&dp_table, input, last_row, best_profit);
+        items.reverse();
+
+        (
+            KnapsackSolution {
+                items,
+                total_value: best_profit,
+            },
+            best_value as u64,
+        )
+    }
+
+    /// Solve the problem with at most `max_items` items in the knapsack (without a limit when
+    /// `max_items` is None)
+    ///
+    /// The table is indexed by the number of items as well as by the profit, holding the minimum
+    /// weight of each (count, profit) pair, so this takes O(n * k * P) time and O(k * P) memory
+    /// (plus n * k * P bits for recovering the items), where k is the limit. Among the optimal
+    /// solutions, the returned one has the minimum total weight.
+    pub fn solve_with_max_items(
+        &self,
+        input: &KnapsackInput,
+        max_items: Option<usize>,
+    ) -> KnapsackSolution {
+        let max_items = match max_items {
+            Some(max_items) if max_items < input.items.len() => max_items,
+            _ => return self.solve(input),
+        };
+
+        // The zero profit items are never needed, and would only take up the allowed count
+        let indices: Vec<usize> = (0..input.items.len())
+            .filter(|&i| input.items[i].profit > 0 && input.items[i].weight <= input.capacity)
+            .collect();
+        let max_profit = indices
+            .iter()
+            .map(|&i| input.items[i].profit as usize)
+            .sum::<usize>();
+        let row_len = max_profit + 1;
+        // The unreachable pairs have a weight higher than the total weight of the items
+        let capacity = DpSolver::weight_limit(input);
+
+        let mut weights = Array2::<u64>::from_elem((max_items + 1, row_len), u64::MAX);
+        weights[(0, 0)] = 0;
+        // Whether taking the item improved each (count, profit) pair, for recovering the items
+        let mut taken = bitvec![0; indices.len() * (max_items + 1) * row_len];
+
+        for (pos, &i) in indices.iter().enumerate() {
+            let item = &input.items[i];
+            let item_profit = item.profit as usize;
+            let offset = pos * (max_items + 1) * row_len;
+
+            for count in (1..=max_items.min(pos + 1)).rev() {
+                for profit in (item_profit..row_len).rev() {
+                    let weight =
+                        weights[(count - 1, profit - item_profit)].saturating_add(item.weight);
+                    if weight < weights[(count, profit)] {
+                        weights[(count, profit)] = weight;
+                        taken.set(offset + count * row_len + profit, true);
+                    }
+                }
+            }
+        }
+
+        // The highest feasible profit, using the count with the minimum weight
+        let (mut count, mut profit) = (0..row_len)
+            .rev()
+            .find_map(|profit| {
+                (0..=max_items)
+                    .filter(|&count| weights[(count, profit)] <= capacity)
+                    .min_by_key(|&count| weights[(count, profit)])
+                    .map(|count| (count, profit))
+            })
+            .unwrap();
+
+        let total_value = profit as u64;
+        let mut items = Vec::with_capacity(count);
+        for (pos, &i) in indices.iter().enumerate().rev() {
+            if count > 0 && taken[pos * (max_items + 1) * row_len + count * row_len + profit] {
+                items.push(i);
+                profit -= input.items[i].profit as usize;
+                count -= 1;
+            }
+        }
+        items.reverse();
+
+        KnapsackSolution { items, total_value }
+    }
+
+    /// Solve the problem, calling `progress` with the number of processed items and the total
+    /// number of items after each item is processed
+    ///
+    /// This is meant for large instances, whose table can take a long time to fill. The callback
+    /// is called once per item, unless the filling stops early because the LP bound is reached.
+    pub fn solve_with_progress(
+        &self,
+        input: &KnapsackInput,
+        progress: impl Fn(usize, usize),
+    ) -> KnapsackSolution {
+        DpSolver::solve_instrumented(input, &mut SolveMetrics::default(), Some(&progress))
+    }
+
+    fn solve_instrumented(
+        input: &KnapsackInput,
+        metrics: &mut SolveMetrics,
+        progress: Option<ProgressCallback>,
+    ) -> KnapsackSolution {
+        let mut items = Vec::new();
+        let total_value = DpSolver::solve_instrumented_into(input, metrics, progress, &mut items);
+
+        KnapsackSolution { items, total_value }
+    }
+
+    /// Solves the problem, writing the selected items into `items` (which is cleared first) and
+    /// returning the total value
+    fn solve_instrumented_into(
+        input: &impl ItemSource,
+        metrics: &mut SolveMetrics,
+        progress: Option<ProgressCallback>,
+        items: &mut Vec<usize>,
+    ) -> u64 {
+        items.clear();
+
+        // Subset-sum instances only need the set of reachable weights
+        if input.items().all(|item| item.profit == item.weight) {
+            let solution = DpSolver::bitset_subset_sum(input, progress);
+            items.extend_from_slice(&solution.items);
+            return solution.total_value;
+        }
+
+        let (dp_table, last_row) = DpSolver::gen_table(input, Some(input.lp_bound()), progress);
+        log_debug!(
+            "dp table of {} x {} cells, filled up to row {}",
+            dp_table.nrows(),
+            dp_table.ncols(),
+            last_row
+        );
+        metrics.record_table_cells(((last_row + 1) * dp_table.ncols()) as u64);
+
+        let capacity = DpSolver::weight_limit(input);
+        let max_profit = dp_table
+            .row(last_row)
+            .iter()
+            .enumerate()
+            .filter(|(_, &weight)| weight <= capacity)
+            .map(|(profit, _)| profit as u64)
+            .max()
+            .unwrap();
+
+        DpSolver::gen_path_into(&dp_table, input, last_row, max_profit, items);
+        // The path is built from the last item to the first one
+        items.reverse();
+
+        max_profit
+    }
+
+    /// Solves a subset-sum instance (every item's profit equals its weight) by computing the set
+    /// of reachable weights as a bitset, which is shifted by the weight of each item and OR-ed
+    /// with itself. This takes O(n * capacity / 64) time instead of filling the whole DP table.
+    ///
+    /// For every weight, the index of the first item that made it reachable is kept, so the items
+    /// are reconstructed with the same tie rule as `gen_path`.
+    fn bitset_subset_sum(
+        input: &impl ItemSource,
+        progress: Option<ProgressCallback>,
+    ) -> KnapsackSolution {
+        let n = input.item_count();
+        let report_progress = |items_done| {
+            if let Some(progress) = progress {
+                progress(items_done, n);
+            }
+        };
+        let limit = DpSolver::weight_limit(input) as usize;
+
+        let mut reachable: BitVec<u64, Lsb0> = bitvec![u64, Lsb0; 0; limit + 1];
+        reachable.set(0, true);
+        // first_item[weight] is the index of the first item with which the weight is reachable
+        let mut first_item = vec![usize::MAX; limit + 1];
+
+        for (i, item) in input.items().enumerate() {
+            let weight = item.weight as usize;
+            if weight == 0 || weight > limit {
+                report_progress(i + 1);
+                continue;
+            }
+
+            let mut shifted = reachable.clone();
+            shifted.shift_right(weight);
+
+            let words = reachable.as_raw_mut_slice().iter_mut();
+            for (word_idx, (word, &shifted_word)) in words.zip(shifted.as_raw_slice()).enumerate() {
+                // Mark the item for the weights that weren't reachable before it
+                let mut new_bits = shifted_word & !*word;
+                while new_bits != 0 {
+                    first_item[word_idx * 64 + new_bits.trailing_zeros() as usize] = i;
+                    new_bits &= new_bits - 1;
+                }
+                *word |= shifted_word;
+            }
+            report_progress(i + 1);
+        }
+
+        let total_value = reachable.last_one().unwrap() as u64;
+
+        let mut items = Vec::new();
+        let mut weight = total_value as usize;
+        while weight > 0 {
+            let item = first_item[weight];
+            items.push(item);
+            weight -= input.item(item).weight as usize;
+        }
+        items.reverse();
+
+        KnapsackSolution { items, total_value }
+    }
 }
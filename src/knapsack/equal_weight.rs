@@ -0,0 +1,60 @@
+use alloc::vec::Vec;
+
+use super::minknap::MinKnapSolver;
+use super::{KnapsackInput, KnapsackMethod, KnapsackSolution, KnapsackSolver, SolverCapabilities};
+
+/// Solver specialized for instances in which all the items have the same weight.
+///
+/// In this case, the optimal solution is given by the items with the highest profits that fit in
+/// the knapsack, which can be found in O(n log n). Instances that do not have equal weights are
+/// delegated to the MinKnap solver.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EqualWeightSolver;
+
+impl EqualWeightSolver {
+    /// Returns the common weight of the items, if all of them have the same weight
+    fn common_weight(input: &KnapsackInput) -> Option<u64> {
+        let weight = input.items.first().map_or(0, |item| item.weight);
+        input
+            .items
+            .iter()
+            .all(|item| item.weight == weight)
+            .then_some(weight)
+    }
+
+    fn solve_equal_weights(input: &KnapsackInput, weight: u64) -> KnapsackSolution {
+        // If the items have zero weight, all of them can be included
+        let item_count = match weight {
+            0 => input.items.len(),
+            _ => (input.capacity / weight) as usize,
+        };
+
+        let mut items: Vec<usize> = (0..input.items.len()).collect();
+        items.sort_by(|&a, &b| input.items[b].profit.cmp(&input.items[a].profit));
+        items.truncate(item_count);
+        items.sort_unstable();
+
+        let total_value = items.iter().map(|&i| input.items[i].profit).sum();
+
+        KnapsackSolution { items, total_value }
+    }
+}
+
+impl KnapsackSolver for EqualWeightSolver {
+    fn solve(&self, input: &KnapsackInput) -> KnapsackSolution {
+        match EqualWeightSolver::common_weight(input) {
+            Some(weight) => EqualWeightSolver::solve_equal_weights(input, weight),
+            None => MinKnapSolver.solve(input),
+        }
+    }
+
+    fn method(&self) -> KnapsackMethod {
+        KnapsackMethod::EqualWeight
+    }
+
+    fn capabilities(&self) -> SolverCapabilities {
+        SolverCapabilities::EXACT
+            | SolverCapabilities::ZERO_WEIGHT_ITEMS
+            | SolverCapabilities::SCALABLE
+    }
+}
@@ -0,0 +1,126 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use ndarray::Array2;
+
+use super::{KnapsackInput, KnapsackInputError, KnapsackSolution};
+
+/// Marks a (layer, profit) cell where no item of the layer is selected
+const NO_CHOICE: usize = usize::MAX;
+
+/// A problem in which at most one item of each exclusion group can be selected
+///
+/// The groups (and the items outside of any group, each one on its own) are processed as the
+/// layers of a profit indexed DP, where each layer either selects one of its items or none.
+#[derive(Debug, Clone)]
+pub struct ExclusiveInput {
+    input: KnapsackInput,
+    // the items of each layer: the exclusion groups, then the items outside of any group
+    layers: Vec<Vec<usize>>,
+}
+
+impl KnapsackInput {
+    /// Allow at most one item of each group to be selected
+    ///
+    /// Fails if an index is out of bounds or if an item belongs to several groups.
+    pub fn with_exclusion_groups(
+        &self,
+        groups: &[Vec<usize>],
+    ) -> Result<ExclusiveInput, KnapsackInputError> {
+        let mut grouped = vec![false; self.items.len()];
+        for &index in groups.iter().flatten() {
+            match grouped.get_mut(index) {
+                Some(true) => return Err(KnapsackInputError::OverlappingExclusionGroups { index }),
+                Some(in_group) => *in_group = true,
+                None => return Err(KnapsackInputError::InvalidExclusionItem { index }),
+            }
+        }
+
+        let layers = groups
+            .iter()
+            .filter(|group| !group.is_empty())
+            .cloned()
+            .chain(
+                (0..self.items.len())
+                    .filter(|&item| !grouped[item])
+                    .map(|item| vec![item]),
+            )
+            .collect();
+
+        Ok(ExclusiveInput {
+            input: self.clone(),
+            layers,
+        })
+    }
+}
+
+impl ExclusiveInput {
+    /// Solve the problem with a DP over the layers, where weights[profit] is the minimum weight
+    /// needed to obtain exactly the given profit with the layers processed so far
+    ///
+    /// The item chosen by each layer for each profit is stored for recovering the solution, so
+    /// this takes O(n * P) time and O(layers * P) memory, where P is the sum of the highest profit
+    /// of each layer.
+    pub fn solve(&self) -> KnapsackSolution {
+        let items = &self.input.items;
+        let max_profit: usize = self
+            .layers
+            .iter()
+            .map(|layer| {
+                layer
+                    .iter()
+                    .map(|&item| items[item].profit as usize)
+                    .max()
+                    .unwrap_or(0)
+            })
+            .sum();
+
+        let mut weights = vec![u64::MAX; max_profit + 1];
+        weights[0] = 0;
+        let mut choices =
+            Array2::<usize>::from_elem((self.layers.len(), max_profit + 1), NO_CHOICE);
+
+        for (layer_idx, layer) in self.layers.iter().enumerate() {
+            let previous = weights.clone();
+            for &item in layer {
+                let (weight, profit) = (items[item].weight, items[item].profit as usize);
+                for p in profit..=max_profit {
+                    let candidate = previous[p - profit].saturating_add(weight);
+                    if candidate < weights[p] {
+                        weights[p] = candidate;
+                        choices[(layer_idx, p)] = item;
+                    }
+                }
+            }
+        }
+
+        // The unreachable profits keep a weight of u64::MAX, higher than the total weight
+        let capacity = self.input.capacity.min(self.input.total_weight());
+        let best_profit = (0..=max_profit)
+            .rev()
+            .find(|&profit| weights[profit] <= capacity)
+            .unwrap_or(0);
+
+        let mut selected_items = Vec::new();
+        let mut profit = best_profit;
+        for layer_idx in (0..self.layers.len()).rev() {
+            let item = choices[(layer_idx, profit)];
+            if item != NO_CHOICE {
+                selected_items.push(item);
+                profit -= items[item].profit as usize;
+            }
+        }
+        selected_items.sort_unstable();
+
+        KnapsackSolution {
+            items: selected_items,
+            total_value: best_profit as u64,
+        }
+    }
+
+    /// The items of each layer of the DP: the exclusion groups, followed by the items outside of
+    /// any group
+    pub fn layers(&self) -> &[Vec<usize>] {
+        &self.layers
+    }
+}
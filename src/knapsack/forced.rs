@@ -0,0 +1,118 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{KnapsackInput, KnapsackInputError, KnapsackSolution, KnapsackSolver};
+
+/// A problem in which some items are forced to be included and others are forced to be excluded
+///
+/// Only the remaining (free) items are given to the solvers, with the capacity reduced by the
+/// weight of the forced items. The solutions are mapped back to the original items, with the
+/// forced items included.
+#[derive(Debug, Clone)]
+pub struct ForcedInput {
+    // the problem on the free items, with the remaining capacity (which may be 0)
+    free_input: KnapsackInput,
+    // original index of each free item
+    free_items: Vec<usize>,
+    // original indices of the forced items
+    forced_items: Vec<usize>,
+    // total profit of the forced items
+    base_profit: u64,
+}
+
+impl KnapsackInput {
+    /// Fix the decisions of some items before solving the problem
+    ///
+    /// The items in `include` are always part of the solution, while the items in `exclude` are
+    /// never part of it. Fails if an index is out of bounds, if an item is both included and
+    /// excluded, or if the included items don't fit in the knapsack.
+    ///
+    /// The indices may be repeated, and the decisions are looked up in masks built once, so this
+    /// takes O(n + k) time for k forced items.
+    pub fn with_forced(
+        &self,
+        include: &[usize],
+        exclude: &[usize],
+    ) -> Result<ForcedInput, KnapsackInputError> {
+        let n = self.items.len();
+        if let Some(&index) = include.iter().chain(exclude).find(|&&index| index >= n) {
+            return Err(KnapsackInputError::InvalidForcedItem { index });
+        }
+
+        let mut included = vec![false; n];
+        for &index in include {
+            included[index] = true;
+        }
+        let mut excluded = vec![false; n];
+        for &index in exclude {
+            if included[index] {
+                return Err(KnapsackInputError::ConflictingForcedItem { index });
+            }
+            excluded[index] = true;
+        }
+
+        let forced_items: Vec<usize> = (0..n).filter(|&i| included[i]).collect();
+
+        // A weight that overflows exceeds any capacity
+        let forced_weight = forced_items
+            .iter()
+            .try_fold(0u64, |total, &i| total.checked_add(self.items[i].weight))
+            .ok_or(KnapsackInputError::InfeasibleForcedItems)?;
+        let base_profit = forced_items.iter().map(|&i| self.items[i].profit).sum();
+
+        let capacity = self
+            .capacity
+            .checked_sub(forced_weight)
+            .ok_or(KnapsackInputError::InfeasibleForcedItems)?;
+
+        let free_items: Vec<usize> = (0..n).filter(|&i| !included[i] && !excluded[i]).collect();
+
+        Ok(ForcedInput {
+            free_input: KnapsackInput {
+                items: free_items.iter().map(|&i| self.items[i]).collect(),
+                capacity,
+                granularity: self.granularity,
+                profit_scale: self.profit_scale,
+                labels: self.select_labels(&free_items),
+            },
+            free_items,
+            forced_items,
+            base_profit,
+        })
+    }
+}
+
+impl ForcedInput {
+    /// Solve the problem on the free items and add the forced items to the solution
+    pub fn solve(&self, solver: &dyn KnapsackSolver) -> KnapsackSolution {
+        let free_solution = if self.free_input.items.is_empty() || self.free_input.capacity == 0 {
+            // Only zero weight items fit, so all of them are taken
+            let items: Vec<usize> = (0..self.free_input.items.len())
+                .filter(|&i| self.free_input.items[i].weight == 0)
+                .collect();
+            let total_value = items.iter().map(|&i| self.free_input.items[i].profit).sum();
+
+            KnapsackSolution { items, total_value }
+        } else {
+            solver.solve(&self.free_input)
+        };
+
+        let mut items: Vec<usize> = free_solution
+            .items
+            .iter()
+            .map(|&i| self.free_items[i])
+            .chain(self.forced_items.iter().copied())
+            .collect();
+        items.sort_unstable();
+
+        KnapsackSolution {
+            items,
+            total_value: free_solution.total_value + self.base_profit,
+        }
+    }
+
+    /// The capacity left for the free items
+    pub fn remaining_capacity(&self) -> u64 {
+        self.free_input.capacity
+    }
+}
@@ -1,22 +1,97 @@
-use super::dp::DpSolver;
-use super::{KnapsackInput, KnapsackItem, KnapsackMethod, KnapsackSolution, KnapsackSolver};
+use alloc::vec::Vec;
 
+use super::dp::{DpSolver, MAX_ADDRESSABLE_CELLS};
+use super::{
+    KnapsackInput, KnapsackItem, KnapsackMethod, KnapsackSolution, KnapsackSolveError,
+    KnapsackSolver, SolverCapabilities,
+};
+
+#[derive(Debug, Clone, Copy, Default)]
 pub struct FptasDpSolver;
 
+/// Buffer for the scaled items of `FptasDpSolver`, reused across solves
+#[derive(Debug, Clone, Default)]
+pub struct FptasScratch {
+    scaled_items: Vec<KnapsackItem>,
+}
+
 impl FptasDpSolver {
-    fn scale_items(input: &KnapsackInput) -> Vec<KnapsackItem> {
-        let max_value = input.items.iter().map(|item| item.profit).max().unwrap();
-        let scale = f64::from(input.granularity * input.items.len() as u32) / max_value as f64;
-        input
+    /// Returns the theoretical guarantee of the solver: the returned value is at least
+    /// `guarantee * OPT`, where OPT is the optimal value
+    ///
+    /// The profits are scaled by K = granularity * n / max_profit and rounded, with an error of
+    /// less than 1 per item, so the value is at least OPT - 2 * max_profit / granularity. This
+    /// gives the ratio 1 - 2 / granularity when the most profitable item fits in the knapsack (so
+    /// that max_profit <= OPT); otherwise, no ratio is guaranteed and 0 is returned.
+    pub fn guarantee(&self, input: &KnapsackInput) -> f64 {
+        let max_profit = input.items.iter().map(|item| item.profit).max();
+        let most_profitable_fits = input
             .items
             .iter()
-            .map(|item| {
-                KnapsackItem::new(
-                    item.weight,
-                    (item.profit as f64 * scale).floor().max(1.0) as u64,
-                )
-            })
-            .collect()
+            .any(|item| Some(item.profit) == max_profit && item.weight <= input.capacity);
+
+        if !most_profitable_fits {
+            return 0.0;
+        }
+        (1.0 - 2.0 / input.granularity as f64).max(0.0)
+    }
+
+    /// Writes the scaled items into `scaled_items`, replacing its content
+    fn scale_items(input: &KnapsackInput, scaled_items: &mut Vec<KnapsackItem>) {
+        let max_value = input.items.iter().map(|item| item.profit).max().unwrap();
+        // The product is computed on floats, so that it can't overflow
+        let scale = f64::from(input.granularity) * input.items.len() as f64 / max_value as f64;
+        scaled_items.clear();
+        scaled_items.extend(input.items.iter().map(|item| {
+            KnapsackItem::new(item.weight, ((item.profit as f64 * scale) as u64).max(1))
+        }));
+    }
+
+    /// Solve the problem (see `try_solve`), keeping the scaled items in `scratch` instead of
+    /// allocating them, so that batches of solves can reuse the same buffer
+    ///
+    /// Only the copy of the items is saved: the DP table is still allocated for every solve.
+    pub fn try_solve_with_scratch(
+        &self,
+        input: &KnapsackInput,
+        scratch: &mut FptasScratch,
+    ) -> Result<KnapsackSolution, KnapsackSolveError> {
+        if input.items.is_empty() {
+            return Ok(KnapsackSolution {
+                items: Vec::new(),
+                total_value: 0,
+            });
+        }
+
+        FptasDpSolver::scale_items(input, &mut scratch.scaled_items);
+        let scaled_input = KnapsackInput::new(
+            core::mem::take(&mut scratch.scaled_items),
+            input.capacity,
+            input.granularity,
+        )
+        .unwrap();
+
+        let scaled_solution = FptasDpSolver::solve_scaled(&scaled_input);
+        // The buffer is given back even if the table is too large
+        scratch.scaled_items = scaled_input.items;
+
+        Ok(FptasDpSolver::get_actual_solution(scaled_solution?, input))
+    }
+
+    /// Solves the scaled problem, using the smaller of the two tables (both give the same solution)
+    fn solve_scaled(scaled_input: &KnapsackInput) -> Result<KnapsackSolution, KnapsackSolveError> {
+        let weight_table_cells = DpSolver::weight_table_cells(scaled_input);
+        if weight_table_cells < DpSolver::table_cells(scaled_input) {
+            if weight_table_cells > MAX_ADDRESSABLE_CELLS as u128 {
+                return Err(KnapsackSolveError::TableTooLarge {
+                    cells: weight_table_cells,
+                    limit: MAX_ADDRESSABLE_CELLS,
+                });
+            }
+            Ok(DpSolver.solve_weight_indexed(scaled_input))
+        } else {
+            DpSolver.try_solve(scaled_input)
+        }
     }
 
     fn get_actual_solution(
@@ -36,17 +111,22 @@ impl FptasDpSolver {
 }
 
 impl KnapsackSolver for FptasDpSolver {
+    /// Panics if the DP table of the scaled problem can't be addressed (see `try_solve`)
     fn solve(&self, input: &KnapsackInput) -> KnapsackSolution {
-        let scaled_items = FptasDpSolver::scale_items(input);
-        let scaled_input =
-            KnapsackInput::new(scaled_items, input.capacity, input.granularity).unwrap();
-
-        let scaled_solution = DpSolver.solve(&scaled_input);
+        self.try_solve(input).unwrap()
+    }
 
-        FptasDpSolver::get_actual_solution(scaled_solution, input)
+    fn try_solve(&self, input: &KnapsackInput) -> Result<KnapsackSolution, KnapsackSolveError> {
+        self.try_solve_with_scratch(input, &mut FptasScratch::default())
     }
 
     fn method(&self) -> KnapsackMethod {
         KnapsackMethod::Fptas
     }
+
+    fn capabilities(&self) -> SolverCapabilities {
+        SolverCapabilities::APPROXIMATE
+            | SolverCapabilities::ZERO_WEIGHT_ITEMS
+            | SolverCapabilities::SCALABLE
+    }
 }
@@ -0,0 +1,94 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::{KnapsackInput, KnapsackItem, KnapsackSolution, KnapsackSolver};
+
+/// A problem in which the identical items are grouped together
+///
+/// The copies of an item form a bounded knapsack item, which is split into bundles of 1, 2, 4, ...
+/// copies (and a last bundle with the remaining ones), so that any number of copies can be
+/// obtained by combining bundles. A group of `m` copies thus only needs about `log2(m)` items in
+/// the 0/1 problem given to the solvers. The solutions are expanded back to the original items.
+#[derive(Debug, Clone)]
+pub struct GroupedInput {
+    // the problem on the bundles
+    grouped_input: KnapsackInput,
+    // original indices of the items, the copies of an item being consecutive
+    members: Vec<usize>,
+    // the range of `members` covered by each bundle
+    bundles: Vec<Range<usize>>,
+}
+
+impl KnapsackInput {
+    /// Group the identical items (with the same weight and profit) into bundles
+    ///
+    /// The labels aren't kept in the grouped problem, since a bundle stands for several items.
+    pub fn group_identical(&self) -> GroupedInput {
+        let mut members: Vec<usize> = (0..self.items.len()).collect();
+        members.sort_by_key(|&i| (self.items[i].weight, self.items[i].profit, i));
+
+        let mut items = Vec::new();
+        let mut bundles = Vec::new();
+        let mut start = 0;
+        while start < members.len() {
+            let item = self.items[members[start]];
+            let end = start
+                + members[start..].partition_point(|&i| {
+                    self.items[i].weight == item.weight && self.items[i].profit == item.profit
+                });
+
+            let mut bundle_size = 1;
+            while start < end {
+                let size = bundle_size.min(end - start);
+                items.push(KnapsackItem {
+                    weight: item.weight.saturating_mul(size as u64),
+                    profit: item.profit.saturating_mul(size as u64),
+                });
+                bundles.push(start..start + size);
+
+                start += size;
+                bundle_size *= 2;
+            }
+        }
+
+        GroupedInput {
+            grouped_input: KnapsackInput {
+                items,
+                labels: Vec::new(),
+                ..*self
+            },
+            members,
+            bundles,
+        }
+    }
+}
+
+impl GroupedInput {
+    /// Solve the problem on the bundles and expand the solution to the original items
+    pub fn solve(&self, solver: &dyn KnapsackSolver) -> KnapsackSolution {
+        if self.grouped_input.items.is_empty() {
+            return KnapsackSolution {
+                items: Vec::new(),
+                total_value: 0,
+            };
+        }
+        let grouped_solution = solver.solve(&self.grouped_input);
+
+        let mut items: Vec<usize> = grouped_solution
+            .items
+            .iter()
+            .flat_map(|&bundle| self.members[self.bundles[bundle].clone()].iter().copied())
+            .collect();
+        items.sort_unstable();
+
+        KnapsackSolution {
+            items,
+            total_value: grouped_solution.total_value,
+        }
+    }
+
+    /// The problem on the bundles, which is given to the solvers
+    pub fn grouped_input(&self) -> &KnapsackInput {
+        &self.grouped_input
+    }
+}
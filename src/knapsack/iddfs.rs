@@ -0,0 +1,110 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{
+    KnapsackInput, KnapsackMethod, KnapsackSolution, KnapsackSolveError, KnapsackSolver,
+    SolverCapabilities,
+};
+
+/// Number of searches performed, with the weight threshold doubling each time until it reaches
+/// the capacity
+const DEEPENING_STEPS: u32 = 4;
+
+/// Backtracking solver that performs successive depth-first searches, allowing an increasing
+/// total weight of the included items, until the threshold reaches the capacity.
+///
+/// The best solution of a search is used as the lower bound of the next one, so the branches that
+/// can't improve it (even by taking all the remaining items) are pruned early. Unlike `BktSolver`,
+/// the search doesn't use an explicit stack of states: only the current decision of each item is
+/// kept, so the extra memory is O(n).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IddfsBktSolver;
+
+impl IddfsBktSolver {
+    /// Search for a solution better than `best` whose weight doesn't exceed the given threshold
+    fn search(
+        input: &KnapsackInput,
+        threshold: u64,
+        remaining_profits: &[u64],
+        best: &mut KnapsackSolution,
+    ) {
+        let n = input.items.len();
+        // The decision taken for each item on the current path
+        let mut included = vec![false; n];
+        let mut depth = 0usize;
+        let mut current_weight = 0u64;
+        let mut current_value = 0u64;
+
+        loop {
+            if depth < n && current_value + remaining_profits[depth] > best.total_value {
+                // Explore the possibility of including the current item first, if it fits
+                let item = &input.items[depth];
+                included[depth] = current_weight + item.weight <= threshold;
+                if included[depth] {
+                    current_weight += item.weight;
+                    current_value += item.profit;
+                }
+                depth += 1;
+                continue;
+            }
+
+            if current_value > best.total_value {
+                *best = KnapsackSolution {
+                    items: (0..depth).filter(|&i| included[i]).collect(),
+                    total_value: current_value,
+                };
+            }
+
+            // Backtrack to the deepest included item and explore the possibility of not
+            // including it
+            loop {
+                if depth == 0 {
+                    return;
+                }
+                depth -= 1;
+                if included[depth] {
+                    included[depth] = false;
+                    current_weight -= input.items[depth].weight;
+                    current_value -= input.items[depth].profit;
+                    depth += 1;
+                    break;
+                }
+            }
+        }
+    }
+}
+
+impl KnapsackSolver for IddfsBktSolver {
+    fn solve(&self, input: &KnapsackInput) -> KnapsackSolution {
+        let n = input.items.len();
+        let mut remaining_profits = vec![0u64; n + 1];
+        for i in (0..n).rev() {
+            remaining_profits[i] = remaining_profits[i + 1] + input.items[i].profit;
+        }
+
+        let mut best = KnapsackSolution {
+            items: Vec::new(),
+            total_value: 0,
+        };
+
+        for step in (0..DEEPENING_STEPS).rev() {
+            let threshold = input.capacity >> step;
+            IddfsBktSolver::search(input, threshold, &remaining_profits, &mut best);
+        }
+
+        best
+    }
+
+    fn try_solve(&self, input: &KnapsackInput) -> Result<KnapsackSolution, KnapsackSolveError> {
+        input.check_totals()?;
+        Ok(self.solve(input))
+    }
+
+    fn method(&self) -> KnapsackMethod {
+        KnapsackMethod::IddfsBkt
+    }
+
+    fn capabilities(&self) -> SolverCapabilities {
+        SolverCapabilities::EXACT | SolverCapabilities::ZERO_WEIGHT_ITEMS
+    }
+}
@@ -1,13 +1,31 @@
-use std::ops::Rem;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::ops::Rem;
 
 use super::{
     sol_tree::{SolutionFragment, SolutionTree},
-    KnapsackInput, KnapsackItem, KnapsackMethod, KnapsackSolution, KnapsackSolver,
+    view::{ItemSource, KnapsackView},
+    KnapsackInput, KnapsackItem, KnapsackMethod, KnapsackSolution, KnapsackSolveError,
+    KnapsackSolver, SolveMetrics, SolverCapabilities,
 };
 use bitvec::prelude::*;
 
+/// Comparator used for ordering the items
+pub type ItemComparator<'c> = &'c dyn Fn(&KnapsackItem, &KnapsackItem) -> Ordering;
+
+/// Compares two items by efficiency (profit/weight), the more efficient item being the lesser one,
+/// so that sorting with this comparator gives a descending efficiency ordering.
+///
+/// The comparison uses integer cross multiplication to avoid floating point errors.
+pub fn efficiency_cmp(a: &KnapsackItem, b: &KnapsackItem) -> Ordering {
+    let eff_a = a.profit as u128 * b.weight as u128;
+    let eff_b = b.profit as u128 * a.weight as u128;
+    eff_b.cmp(&eff_a)
+}
+
 /// This function prepares the items for the minknap algorithm.
-/// It sorts the items by efficiency, calculated as value/weight.
+/// It sorts the items using the given comparator (by default, by efficiency, calculated as value/weight).
 /// It also filters out items that are too heavy to be included in the knapsack,
 /// and implicitly includes all items that have zero weight.
 ///
@@ -15,13 +33,15 @@ use bitvec::prelude::*;
 /// - A vector of ItemEfficiency structs, sorted by efficiency
 /// - A BitVec representing the decision vector, with true values for items that are included (it is used for including the zero weight items)
 /// - The total profit of the included zero weight items
-fn prepare_items(input: &KnapsackInput) -> (Vec<usize>, BitVec, u64) {
-    let mut decision_vec = bitvec![0; input.items.len()];
+pub(super) fn prepare_items(
+    input: &impl ItemSource,
+    cmp: ItemComparator,
+) -> (Vec<usize>, BitVec, u64) {
+    let mut decision_vec = bitvec![0; input.item_count()];
     let mut base_profit = 0u64;
 
     let mut items: Vec<usize> = input
-        .items
-        .iter()
+        .items()
         .enumerate()
         .filter_map(|(i, item)| {
             if item.weight == 0 {
@@ -29,7 +49,7 @@ fn prepare_items(input: &KnapsackInput) -> (Vec<usize>, BitVec, u64) {
                 base_profit += item.profit;
                 return None;
             }
-            if item.weight <= input.capacity {
+            if item.weight <= input.capacity() {
                 Some(i)
             } else {
                 None
@@ -37,25 +57,27 @@ fn prepare_items(input: &KnapsackInput) -> (Vec<usize>, BitVec, u64) {
         })
         .collect();
 
-    //items.sort_by(|a, b| b.efficiency.partial_cmp(&a.efficiency).unwrap());
-    // Sort the items by efficiency in descending order, without using floats
-    items.sort_by(|a, b| {
-        let item_a = &input.items[*a];
-        let item_b = &input.items[*b];
-        let eff_a = item_a.profit * item_b.weight;
-        let eff_b = item_b.profit * item_a.weight;
-        eff_b.cmp(&eff_a)
-    });
+    items.sort_by(|a, b| cmp(&input.item(*a), &input.item(*b)));
 
     (items, decision_vec, base_profit)
 }
 
+/// Computes (weight * item.profit) / item.weight, the profit of the given weight taken from a
+/// fraction of the item.
+///
+/// The product is computed on 128 bits so that it can't overflow, and the result saturates at
+/// u64::MAX. An overflow here would corrupt the upper bound and could prune the optimal solution.
+pub(super) fn scaled_profit(weight: u64, item: &KnapsackItem) -> u64 {
+    let profit = (weight as u128 * item.profit as u128) / item.weight as u128;
+    u64::try_from(profit).unwrap_or(u64::MAX)
+}
+
 #[derive(Default)]
-struct BreakSolution {
-    break_index: usize,
+pub(super) struct BreakSolution {
+    pub(super) break_index: usize,
     // The total profit of the items that have been included (integral part only)
-    total_profit: u64,
-    total_weight: u64,
+    pub(super) total_profit: u64,
+    pub(super) total_weight: u64,
 }
 
 impl BreakSolution {
@@ -67,8 +89,8 @@ impl BreakSolution {
     /// decision_vec - the decision vector
     ///
     /// The item_efficiencies and decision_vec should be the same as the ones returned by prepare_items.
-    fn new(
-        input: &KnapsackInput,
+    pub(super) fn new(
+        input: &impl ItemSource,
         efficiency_ordering: &[usize],
         decision_vec: &mut BitSlice,
     ) -> Self {
@@ -79,8 +101,8 @@ impl BreakSolution {
         let mut result = BreakSolution::default();
 
         while i < efficiency_ordering.len() {
-            let item = &input.items[efficiency_ordering[i]];
-            if total_weight + item.weight <= input.capacity {
+            let item = input.item(efficiency_ordering[i]);
+            if item.weight <= input.capacity() - total_weight {
                 total_weight += item.weight;
                 total_profit += item.profit;
                 decision_vec.set(efficiency_ordering[i], true);
@@ -106,11 +128,18 @@ impl BreakSolution {
             };
         }
 
+        log_debug!(
+            "break solution: break index {} of {}, profit {}, weight {}",
+            result.break_index,
+            efficiency_ordering.len(),
+            result.total_profit,
+            result.total_weight
+        );
         result
     }
 }
 
-struct MinKnapInstance<'a> {
+struct MinKnapInstance<'a, I: ItemSource> {
     /// The weight of the best solution found so far
     best_sol_weight: u64,
     /// A bit vector representing the items included in the best solution
@@ -118,10 +147,13 @@ struct MinKnapInstance<'a> {
     /// A vector containing the  indices of the items sorted by efficiency
     efficiency_ordering: Vec<usize>,
     /// The total profit of the implicitly included zero weight items
+    ///
+    /// These items are not part of the efficiency ordering, so their profit is never counted in
+    /// the states and it is added exactly once, to the final profit.
     base_profit: u64,
     /// The break solution
     break_solution: BreakSolution,
-    problem_instance: &'a KnapsackInput,
+    problem_instance: &'a I,
     /// The lower bound of the core problem
     s: usize,
     /// The upper bound of the core problem
@@ -140,6 +172,10 @@ struct MinKnapInstance<'a> {
     best_sol_fragment: SolutionFragment,
     /// The solution tree constructed out of fragments
     solution_tree: SolutionTree,
+    /// For orderings other than the efficiency one, the total profit of the items from each
+    /// position of the ordering to its end, used for computing a bound that doesn't depend on
+    /// the ordering
+    remaining_profits: Option<Vec<u64>>,
 }
 
 #[derive(Clone, Copy)]
@@ -149,12 +185,42 @@ struct MinKnapState {
     sol_fragment: SolutionFragment,
 }
 
-impl<'a> MinKnapInstance<'a> {
-    fn new(input: &'a KnapsackInput) -> Self {
-        let (efficiency_ordering, mut decision_vec, base_profit) = prepare_items(input);
+impl<'a, I: ItemSource> MinKnapInstance<'a, I> {
+    fn new(input: &'a I, custom_cmp: Option<ItemComparator>) -> Self {
+        let (efficiency_ordering, decision_vec, base_profit) =
+            prepare_items(input, custom_cmp.unwrap_or(&efficiency_cmp));
+        let remaining_profits = custom_cmp.map(|_| {
+            let mut remaining = vec![0u64; efficiency_ordering.len() + 1];
+            for i in (0..efficiency_ordering.len()).rev() {
+                remaining[i] = remaining[i + 1] + input.item(efficiency_ordering[i]).profit;
+            }
+            remaining
+        });
+        MinKnapInstance::from_prepared(
+            input,
+            efficiency_ordering,
+            decision_vec,
+            base_profit,
+            remaining_profits,
+        )
+    }
+
+    /// Creates the instance from the candidate items already sorted (see `prepare_items`)
+    fn from_prepared(
+        input: &'a I,
+        efficiency_ordering: Vec<usize>,
+        mut decision_vec: BitVec,
+        base_profit: u64,
+        remaining_profits: Option<Vec<u64>>,
+    ) -> Self {
         let break_solution =
             BreakSolution::new(input, &efficiency_ordering, decision_vec.as_mut_bitslice());
-        let max_allowed_weight = input.capacity + break_solution.total_weight;
+        // Saturate instead of overflowing for huge capacities. The bound stays below u64::MAX, so
+        // that the states whose weight saturated while adding an item are always discarded
+        let max_allowed_weight = input
+            .capacity()
+            .saturating_add(break_solution.total_weight)
+            .min(u64::MAX - 1);
 
         // Initially, our best solution is the break solution
         // From the book "Knapsack Problems" (p137), for the Primal-Dual DP Algorithm, the observation is that, generally, only a few items around the break
@@ -167,9 +233,13 @@ impl<'a> MinKnapInstance<'a> {
         //
         // So z_s,t(d) is an optimal solution defined on items j=s..t, where items j<s have been
         // fixed to 1, and items j>t have been fixed to 0.
+        //
+        // When there is no break item (b is the number of candidate items, possibly 0), the break
+        // solution is optimal and the core is never explored, so t is only clamped to avoid an
+        // underflow.
         let b = break_solution.break_index;
         let s = b;
-        let t = b - 1;
+        let t = b.saturating_sub(1);
 
         let profit_lower_bound = break_solution.total_profit;
         let best_sol_weight = break_solution.total_weight;
@@ -189,19 +259,30 @@ impl<'a> MinKnapInstance<'a> {
             best_sol_item: 0,
             solution_tree: SolutionTree::new(),
             best_sol_fragment: SolutionFragment::default(),
+            remaining_profits,
         }
     }
 
     /// Returns the item at the given efficiency order index
     fn get_item(&self, efficiency_order_idx: usize) -> KnapsackItem {
-        self.problem_instance.items[self.efficiency_ordering[efficiency_order_idx]]
+        self.problem_instance
+            .item(self.efficiency_ordering[efficiency_order_idx])
     }
 
     /// Returns the upper bound of the core problem with the given current bounds [s, t] and state
     fn get_profit_upper_bound(&self, current_state: &MinKnapState, s: usize, t: usize) -> u64 {
-        if current_state.weight <= self.problem_instance.capacity {
+        if let Some(remaining_profits) = &self.remaining_profits {
+            // Without the efficiency ordering, the linear bounds below are not valid anymore, so
+            // the bound is given by adding all the items that haven't been considered yet
+            // (removing items can only decrease the profit)
+            return current_state
+                .profit
+                .saturating_add(remaining_profits[(t + 1).min(self.efficiency_ordering.len())]);
+        }
+
+        if current_state.weight <= self.problem_instance.capacity() {
             // Under capacity, we can try expanding the core by including the next item after t
-            if t + 1 >= self.problem_instance.items.len() - 1 {
+            if t + 1 >= self.efficiency_ordering.len() {
                 // If we are already at the last item, we can't expand the core anymore
                 current_state.profit
             } else {
@@ -209,10 +290,12 @@ impl<'a> MinKnapInstance<'a> {
                 // Use the integer arithmetic to avoid floating point errors
                 // (weight_diff * profit) / weight
 
-                let weight_diff = self.problem_instance.capacity - current_state.weight;
+                let weight_diff = self.problem_instance.capacity() - current_state.weight;
                 let next_item = self.get_item(t + 1);
 
-                current_state.profit + (weight_diff * next_item.profit) / next_item.weight
+                current_state
+                    .profit
+                    .saturating_add(scaled_profit(weight_diff, &next_item))
             }
         } else {
             // Over capacity, we can try reducing the core by excluding the next item after s
@@ -222,12 +305,12 @@ impl<'a> MinKnapInstance<'a> {
                 current_state.profit
             } else {
                 // Try linearly reducing the core, using the same integer arithmetic as above
-                let weight_diff = current_state.weight - self.problem_instance.capacity;
+                let weight_diff = current_state.weight - self.problem_instance.capacity();
                 let prev_item = self.get_item(s - 1);
 
                 current_state
                     .profit
-                    .saturating_sub((weight_diff * prev_item.profit) / prev_item.weight)
+                    .saturating_sub(scaled_profit(weight_diff, &prev_item))
             }
         }
     }
@@ -235,7 +318,8 @@ impl<'a> MinKnapInstance<'a> {
     /// Try updating the best profit found so far with the given state
     /// This should be called only on newly found feasible states
     fn try_update_lower_bound(&mut self, state: &MinKnapState) {
-        if state.weight <= self.problem_instance.capacity && state.profit > self.profit_lower_bound
+        if state.weight <= self.problem_instance.capacity()
+            && state.profit > self.profit_lower_bound
         {
             self.profit_lower_bound = state.profit;
             self.best_sol_weight = state.weight;
@@ -286,11 +370,15 @@ impl<'a> MinKnapInstance<'a> {
             // The profit ordering is maintained by the fact that we are discarding dominated states
             if no_insert_index >= state_count
                 || current_states[no_insert_index].weight
-                    > current_states[insert_index].weight + item.weight
+                    > current_states[insert_index]
+                        .weight
+                        .saturating_add(item.weight)
             {
                 // The new state that we would get by including the item in the current state
                 let mut new_state = MinKnapState {
-                    weight: current_states[insert_index].weight + item.weight,
+                    weight: current_states[insert_index]
+                        .weight
+                        .saturating_add(item.weight),
                     profit: current_states[insert_index].profit + item.profit,
                     ..current_states[insert_index]
                 };
@@ -502,11 +590,13 @@ impl<'a> MinKnapInstance<'a> {
         next_states: &mut Vec<MinKnapState>,
     ) {
         current_states.clear();
-        std::mem::swap(current_states, next_states);
+        core::mem::swap(current_states, next_states);
     }
 
     fn update_solution_history(&mut self, current_states: &mut [MinKnapState]) {
         if self.traversal_order.len().rem(u64::BITS as usize) == 0 {
+            // Every state pushes its fragment, so the tree grows at most once per round
+            self.solution_tree.reserve(current_states.len());
             current_states.iter_mut().for_each(|state| {
                 let prev_fragment_idx = self.solution_tree.push_fragment(state.sol_fragment);
                 state
@@ -570,12 +660,24 @@ impl<'a> MinKnapInstance<'a> {
         }
     }
 
+    /// Record the memory reserved for both state buffers
+    fn record_state_bytes(
+        current_states: &Vec<MinKnapState>,
+        next_states: &Vec<MinKnapState>,
+        metrics: &mut SolveMetrics,
+    ) {
+        let states = current_states.capacity() + next_states.capacity();
+        metrics.record_state_bytes((states * core::mem::size_of::<MinKnapState>()) as u64);
+    }
+
     /// Solve the problem, returning the best profit found and its corresponding weight
-    fn solve(mut self) -> (u64, u64, BitVec) {
+    fn solve(mut self, metrics: &mut SolveMetrics) -> (u64, u64, BitVec) {
         // Check the edge case when the break solution is already the best solution
-        if self.break_solution.break_index == self.problem_instance.items.len() {
+        // Only the items in the efficiency ordering are candidates (zero weight items are already
+        // included and items heavier than the capacity are excluded)
+        if self.break_solution.break_index == self.efficiency_ordering.len() {
             return (
-                self.break_solution.total_profit,
+                self.break_solution.total_profit + self.base_profit,
                 self.break_solution.total_weight,
                 self.decision_vec,
             );
@@ -585,7 +687,7 @@ impl<'a> MinKnapInstance<'a> {
         let mut next_states = Vec::<MinKnapState>::new();
 
         let mut visited_items_count = 0usize;
-        let n = self.problem_instance.items.len();
+        let n = self.efficiency_ordering.len();
 
         current_states.push(MinKnapState {
             weight: self.break_solution.total_weight,
@@ -597,6 +699,12 @@ impl<'a> MinKnapInstance<'a> {
             if self.t + 1 < n {
                 self.t += 1;
                 self.explore_item_t(&mut current_states, &mut next_states);
+                log_trace!(
+                    "core expanded to t = {}: {} states",
+                    self.t,
+                    next_states.len()
+                );
+                Self::record_state_bytes(&current_states, &next_states, metrics);
                 self.swap_state_buffers(&mut current_states, &mut next_states);
                 self.update_solution_history(&mut current_states);
 
@@ -606,6 +714,12 @@ impl<'a> MinKnapInstance<'a> {
             if self.s > 0 {
                 self.s -= 1;
                 self.explore_item_s(&mut current_states, &mut next_states);
+                log_trace!(
+                    "core expanded to s = {}: {} states",
+                    self.s,
+                    next_states.len()
+                );
+                Self::record_state_bytes(&current_states, &next_states, metrics);
                 self.swap_state_buffers(&mut current_states, &mut next_states);
                 self.update_solution_history(&mut current_states);
 
@@ -613,6 +727,15 @@ impl<'a> MinKnapInstance<'a> {
             }
         }
 
+        log_debug!(
+            "min-knap explored a core of {} of {} items, best profit {}",
+            visited_items_count,
+            n,
+            self.profit_lower_bound + self.base_profit
+        );
+        metrics.record_core(visited_items_count, n);
+        metrics.record_solution_tree_bytes(self.solution_tree.reserved_bytes() as u64);
+        // The tree is dropped along with the instance, right after the reconstruction
         self.reconstruct_solution();
 
         (
@@ -623,12 +746,44 @@ impl<'a> MinKnapInstance<'a> {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default)]
 pub struct MinKnapSolver;
 
-impl KnapsackSolver for MinKnapSolver {
-    fn solve(&self, input: &KnapsackInput) -> KnapsackSolution {
-        let instance = MinKnapInstance::new(input);
-        let (profit, weight, decision_vec) = instance.solve();
+impl MinKnapSolver {
+    /// Solve the problem using a custom ordering of the items instead of the efficiency one
+    ///
+    /// The ordering only changes the break solution and the order in which the items are
+    /// considered, so the result is still optimal. However, the efficiency based bounds can't be
+    /// used with other orderings, so the search is usually slower.
+    pub fn solve_with_ordering(
+        &self,
+        input: &KnapsackInput,
+        cmp: impl Fn(&KnapsackItem, &KnapsackItem) -> Ordering,
+    ) -> KnapsackSolution {
+        MinKnapSolver::solve_instance(
+            MinKnapInstance::new(input, Some(&cmp)),
+            &mut SolveMetrics::default(),
+        )
+    }
+
+    /// Solve the problem, returning the peak memory of the state buffers and the size of the core
+    /// alongside the solution
+    ///
+    /// `full_core` is set when every item had to be explored, in which case the core heuristic
+    /// gave no speedup and another method (e.g. `BalKnapSolver`) may be faster.
+    #[cfg(feature = "metrics")]
+    pub fn solve_with_metrics(&self, input: &KnapsackInput) -> (KnapsackSolution, SolveMetrics) {
+        let mut metrics = SolveMetrics::default();
+        let solution =
+            MinKnapSolver::solve_instance(MinKnapInstance::new(input, None), &mut metrics);
+        (solution, metrics)
+    }
+
+    fn solve_instance(
+        instance: MinKnapInstance<impl ItemSource>,
+        metrics: &mut SolveMetrics,
+    ) -> KnapsackSolution {
+        let (profit, _weight, decision_vec) = instance.solve(metrics);
 
         let selected_items: Vec<usize> = decision_vec
             .iter()
@@ -641,8 +796,108 @@ impl KnapsackSolver for MinKnapSolver {
             total_value: profit,
         }
     }
+}
+
+/// MinKnap solver keeping the efficiency ordering of the last item set it solved
+///
+/// Solving the same items with several capacities only sorts them once: the ordering is only
+/// computed again when the items change. The break solution depends on the capacity, so it is
+/// still computed for every solve. The solutions are the same as the ones of `MinKnapSolver`.
+#[derive(Debug, Clone, Default)]
+pub struct CachedMinKnapSolver {
+    /// The items of the cached ordering
+    items: Vec<KnapsackItem>,
+    /// The indices of the positive weight items, sorted by efficiency
+    ordering: Vec<usize>,
+}
+
+impl CachedMinKnapSolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn solve(&mut self, input: &KnapsackInput) -> KnapsackSolution {
+        let cached = self.items.len() == input.items.len()
+            && self
+                .items
+                .iter()
+                .zip(&input.items)
+                .all(|(a, b)| a.weight == b.weight && a.profit == b.profit);
+        if !cached {
+            self.items.clone_from(&input.items);
+            self.ordering = (0..input.items.len())
+                .filter(|&i| input.items[i].weight > 0)
+                .collect();
+            self.ordering
+                .sort_by(|&a, &b| efficiency_cmp(&input.items[a], &input.items[b]));
+        }
+
+        // Same as prepare_items, with the sorting replaced by a filter of the cached ordering
+        let mut decision_vec = bitvec![0; input.items.len()];
+        let mut base_profit = 0u64;
+        for (i, item) in input.items.iter().enumerate() {
+            if item.weight == 0 {
+                decision_vec.set(i, true);
+                base_profit += item.profit;
+            }
+        }
+        let efficiency_ordering = self
+            .ordering
+            .iter()
+            .copied()
+            .filter(|&i| input.items[i].weight <= input.capacity)
+            .collect();
+
+        MinKnapSolver::solve_instance(
+            MinKnapInstance::from_prepared(
+                input,
+                efficiency_ordering,
+                decision_vec,
+                base_profit,
+                None,
+            ),
+            &mut SolveMetrics::default(),
+        )
+    }
+}
+
+impl KnapsackSolver for MinKnapSolver {
+    fn solve(&self, input: &KnapsackInput) -> KnapsackSolution {
+        MinKnapSolver::solve_instance(
+            MinKnapInstance::new(input, None),
+            &mut SolveMetrics::default(),
+        )
+    }
+
+    fn try_solve(&self, input: &KnapsackInput) -> Result<KnapsackSolution, KnapsackSolveError> {
+        input.check_totals()?;
+        Ok(self.solve(input))
+    }
+
+    /// The items are read straight from the viewed slices, without copying them
+    fn solve_view(&self, view: &KnapsackView) -> KnapsackSolution {
+        MinKnapSolver::solve_instance(
+            MinKnapInstance::new(view, None),
+            &mut SolveMetrics::default(),
+        )
+    }
+
+    fn solve_into(&self, input: &KnapsackInput, items_buf: &mut Vec<usize>) -> u64 {
+        let (profit, _weight, decision_vec) =
+            MinKnapInstance::new(input, None).solve(&mut SolveMetrics::default());
+
+        items_buf.clear();
+        items_buf.extend(decision_vec.iter_ones());
+        profit
+    }
 
     fn method(&self) -> KnapsackMethod {
         KnapsackMethod::MinKnap
     }
+
+    fn capabilities(&self) -> SolverCapabilities {
+        SolverCapabilities::EXACT
+            | SolverCapabilities::ZERO_WEIGHT_ITEMS
+            | SolverCapabilities::SCALABLE
+    }
 }
@@ -0,0 +1,265 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{view::ItemSource, KnapsackInput, KnapsackInputError, KnapsackItem, KnapsackSolution};
+
+#[derive(Debug, Clone)]
+pub struct MultiDimItem {
+    profit: u64,
+    // the weight of the item along each dimension
+    weights: Vec<u64>,
+}
+
+impl MultiDimItem {
+    pub fn new(weights: Vec<u64>, profit: u64) -> Self {
+        MultiDimItem { profit, weights }
+    }
+
+    pub fn profit(&self) -> u64 {
+        self.profit
+    }
+
+    /// The weight of the item along each dimension
+    pub fn weights(&self) -> &[u64] {
+        &self.weights
+    }
+}
+
+/// Input for the multidimensional knapsack problem, in which every item has a weight along each
+/// of the dimensions and the knapsack has a capacity for each of them
+#[derive(Debug, Clone)]
+pub struct MultiDimKnapsackInput {
+    items: Vec<MultiDimItem>,
+    capacities: Vec<u64>,
+}
+
+impl MultiDimKnapsackInput {
+    /// Fails if the dimensions of an item don't match the ones of the knapsack, or if the total
+    /// profit of the items overflows (the solver adds up the profits without checking them)
+    pub fn new(items: Vec<MultiDimItem>, capacities: Vec<u64>) -> Result<Self, KnapsackInputError> {
+        if capacities.is_empty() {
+            return Err(KnapsackInputError::MissingCapacity);
+        }
+        if capacities.contains(&0) {
+            return Err(KnapsackInputError::ZeroCapacity);
+        }
+        if items
+            .iter()
+            .any(|item| item.weights.len() != capacities.len())
+        {
+            return Err(KnapsackInputError::DimensionMismatch);
+        }
+        if items
+            .iter()
+            .try_fold(0u64, |total, item| total.checked_add(item.profit))
+            .is_none()
+        {
+            return Err(KnapsackInputError::ProfitOverflow);
+        }
+
+        Ok(MultiDimKnapsackInput { items, capacities })
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.capacities.len()
+    }
+
+    pub fn items(&self) -> &[MultiDimItem] {
+        &self.items
+    }
+
+    /// The capacity of the knapsack along each dimension
+    pub fn capacities(&self) -> &[u64] {
+        &self.capacities
+    }
+}
+
+impl TryFrom<&KnapsackInput> for MultiDimKnapsackInput {
+    type Error = KnapsackInputError;
+
+    /// The single dimension problem is the special case with only one dimension
+    fn try_from(input: &KnapsackInput) -> Result<Self, Self::Error> {
+        let items = input
+            .items
+            .iter()
+            .map(|item| MultiDimItem::new(vec![item.weight], item.profit))
+            .collect();
+
+        MultiDimKnapsackInput::new(items, vec![input.capacity])
+    }
+}
+
+/// Upper bound used by `MultiDimBnbSolver` to prune the partial solutions
+pub trait UpperBound {
+    /// Returns an upper bound of the profit that the items from `next_item` onwards can add to a
+    /// partial solution, given the capacities it leaves along each dimension
+    fn bound(
+        &self,
+        input: &MultiDimKnapsackInput,
+        next_item: usize,
+        remaining_capacities: &[u64],
+    ) -> u64;
+}
+
+/// Dantzig bound of the LP relaxation, taken along each dimension separately
+///
+/// Each dimension on its own is a relaxation of the problem, so the lowest of their Dantzig bounds
+/// (see `KnapsackInput::lp_bound`) is a bound of the problem.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LpBound;
+
+/// The single dimension problem of the remaining items along one dimension
+struct DimensionRelaxation {
+    items: Vec<KnapsackItem>,
+    capacity: u64,
+}
+
+impl ItemSource for DimensionRelaxation {
+    fn item_count(&self) -> usize {
+        self.items.len()
+    }
+
+    fn item(&self, index: usize) -> KnapsackItem {
+        self.items[index]
+    }
+
+    fn capacity(&self) -> u64 {
+        self.capacity
+    }
+}
+
+impl UpperBound for LpBound {
+    fn bound(
+        &self,
+        input: &MultiDimKnapsackInput,
+        next_item: usize,
+        remaining_capacities: &[u64],
+    ) -> u64 {
+        let remaining_items = &input.items[next_item..];
+        remaining_capacities
+            .iter()
+            .enumerate()
+            .map(|(dimension, &capacity)| {
+                DimensionRelaxation {
+                    items: remaining_items
+                        .iter()
+                        .map(|item| KnapsackItem::new(item.weights[dimension], item.profit))
+                        .collect(),
+                    capacity,
+                }
+                .lp_bound()
+            })
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+/// Branch and bound solver for the multidimensional knapsack problem.
+///
+/// The search space is explored depth-first, pruning the branches whose upper bound (`LpBound` by
+/// default) can't improve the best solution found so far. The running time is exponential in the
+/// worst case, so it is only suitable for small instances.
+#[derive(Clone, Copy)]
+pub struct MultiDimBnbSolver<'b> {
+    bound: &'b dyn UpperBound,
+}
+
+impl<'b> MultiDimBnbSolver<'b> {
+    /// Creates a solver pruning the partial solutions with the given bound
+    pub fn new(bound: &'b dyn UpperBound) -> Self {
+        MultiDimBnbSolver { bound }
+    }
+}
+
+impl Default for MultiDimBnbSolver<'_> {
+    fn default() -> Self {
+        MultiDimBnbSolver::new(&LpBound)
+    }
+}
+
+impl core::fmt::Debug for MultiDimBnbSolver<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MultiDimBnbSolver").finish_non_exhaustive()
+    }
+}
+
+struct BnbState<'a> {
+    input: &'a MultiDimKnapsackInput,
+    bound: &'a dyn UpperBound,
+    remaining_capacities: Vec<u64>,
+    current_items: Vec<usize>,
+    current_profit: u64,
+    best_solution: KnapsackSolution,
+}
+
+impl BnbState<'_> {
+    fn fits(&self, item: &MultiDimItem) -> bool {
+        item.weights
+            .iter()
+            .zip(&self.remaining_capacities)
+            .all(|(weight, capacity)| weight <= capacity)
+    }
+
+    fn explore(&mut self, idx: usize) {
+        if self.current_profit > self.best_solution.total_value {
+            self.best_solution = KnapsackSolution {
+                items: self.current_items.clone(),
+                total_value: self.current_profit,
+            };
+        }
+
+        if idx == self.input.items.len() {
+            return;
+        }
+        let bound = self
+            .bound
+            .bound(self.input, idx, &self.remaining_capacities);
+        if self.current_profit.saturating_add(bound) <= self.best_solution.total_value {
+            return;
+        }
+
+        let item = &self.input.items[idx];
+
+        // Explore the possibility of including the current item
+        if self.fits(item) {
+            self.remaining_capacities
+                .iter_mut()
+                .zip(&item.weights)
+                .for_each(|(capacity, weight)| *capacity -= weight);
+            self.current_items.push(idx);
+            self.current_profit += item.profit;
+
+            self.explore(idx + 1);
+
+            self.current_profit -= item.profit;
+            self.current_items.pop();
+            self.remaining_capacities
+                .iter_mut()
+                .zip(&item.weights)
+                .for_each(|(capacity, weight)| *capacity += weight);
+        }
+
+        // Explore the possibility of not including the current item
+        self.explore(idx + 1);
+    }
+}
+
+impl MultiDimBnbSolver<'_> {
+    pub fn solve(&self, input: &MultiDimKnapsackInput) -> KnapsackSolution {
+        let mut state = BnbState {
+            input,
+            bound: self.bound,
+            remaining_capacities: input.capacities.clone(),
+            current_items: Vec::new(),
+            current_profit: 0,
+            best_solution: KnapsackSolution {
+                items: Vec::new(),
+                total_value: 0,
+            },
+        };
+
+        state.explore(0);
+
+        state.best_solution
+    }
+}
@@ -0,0 +1,85 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use serde::Serialize;
+
+use super::minknap::MinKnapSolver;
+use super::{KnapsackInput, KnapsackItem, KnapsackSolver};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MultiKnapsackSolution {
+    // for each knapsack, the indices of the items assigned to it
+    pub assignments: Vec<Vec<usize>>,
+    // total value/profit of the items assigned to all knapsacks
+    pub total_value: u64,
+}
+
+/// Heuristic solver for the multiple knapsack problem, in which the items have to be packed into
+/// several knapsacks of given capacities, maximizing the total profit.
+///
+/// The knapsacks are filled one at a time, in descending order of their capacities, by solving
+/// exactly (using MinKnap) the single knapsack problem on the items that have not been assigned
+/// yet. This is not guaranteed to be optimal, but it is fast and usually close to the optimum.
+#[derive(Debug, Clone)]
+pub struct MultipleKnapsackSolver {
+    capacities: Vec<u64>,
+}
+
+impl MultipleKnapsackSolver {
+    pub fn new(capacities: Vec<u64>) -> Self {
+        MultipleKnapsackSolver { capacities }
+    }
+
+    pub fn solve(&self, items: &[KnapsackItem]) -> MultiKnapsackSolution {
+        let mut assignments = vec![Vec::new(); self.capacities.len()];
+        let mut total_value = 0u64;
+        let mut assigned = vec![false; items.len()];
+
+        let mut knapsack_order: Vec<usize> = (0..self.capacities.len()).collect();
+        knapsack_order.sort_by_key(|&k| core::cmp::Reverse(self.capacities[k]));
+
+        // Zero weight items fit in any knapsack, so they are all assigned to the first one
+        if let Some(&first) = knapsack_order.first() {
+            for (i, item) in items.iter().enumerate() {
+                if item.weight == 0 {
+                    assigned[i] = true;
+                    assignments[first].push(i);
+                    total_value += item.profit;
+                }
+            }
+        }
+
+        for k in knapsack_order {
+            let capacity = self.capacities[k];
+
+            // Only the unassigned items that fit in the current knapsack are considered
+            let candidates: Vec<usize> = (0..items.len())
+                .filter(|&i| !assigned[i] && items[i].weight <= capacity)
+                .collect();
+
+            if candidates.is_empty() {
+                continue;
+            }
+
+            let sub_items = candidates.iter().map(|&i| items[i]).collect();
+            let Ok(sub_input) = KnapsackInput::new(sub_items, capacity, 1) else {
+                continue;
+            };
+
+            let solution = MinKnapSolver.solve(&sub_input);
+
+            for &sub_idx in &solution.items {
+                let item_idx = candidates[sub_idx];
+                assigned[item_idx] = true;
+                assignments[k].push(item_idx);
+            }
+            assignments[k].sort_unstable();
+            total_value += solution.total_value;
+        }
+
+        MultiKnapsackSolution {
+            assignments,
+            total_value,
+        }
+    }
+}
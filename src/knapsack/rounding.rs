@@ -0,0 +1,167 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{
+    minknap::{efficiency_cmp, prepare_items, BreakSolution},
+    KnapsackInput, KnapsackMethod, KnapsackSolution, KnapsackSolver, SolverCapabilities,
+};
+
+/// Approximation solver that randomly rounds the solution of the LP relaxation.
+///
+/// The LP solution is the break solution of the efficiency ordering, in which only the break item
+/// is fractional. Each trial includes the fractional item with probability equal to its fraction,
+/// repairing the solution by dropping the least efficient of the other included items until it
+/// fits. The LP relaxation of the items after it and of the remaining capacity then has its own
+/// fractional item, which is rounded the same way, until all the items are decided. The capacity
+/// freed by the repairs is filled greedily in efficiency order, and the best solution over all the
+/// trials is returned.
+///
+/// The trials are drawn from a generator seeded with `seed`, so the results are reproducible.
+#[derive(Debug, Clone, Copy)]
+pub struct RandomizedRoundingSolver {
+    trials: u32,
+    seed: u64,
+}
+
+impl RandomizedRoundingSolver {
+    pub const DEFAULT_TRIALS: u32 = 16;
+    pub const DEFAULT_SEED: u64 = 0;
+
+    /// Creates a solver running the given number of trials (at least one trial is always run)
+    pub const fn new(trials: u32, seed: u64) -> Self {
+        RandomizedRoundingSolver { trials, seed }
+    }
+}
+
+impl Default for RandomizedRoundingSolver {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TRIALS, Self::DEFAULT_SEED)
+    }
+}
+
+/// SplitMix64 generator, which is enough for drawing the rounding decisions
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a uniformly distributed value in [0, 1)
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl RandomizedRoundingSolver {
+    /// Runs a single trial, returning the profit and the included positions of the efficiency
+    /// ordering
+    fn round(
+        input: &KnapsackInput,
+        efficiency_ordering: &[usize],
+        break_solution: &BreakSolution,
+        rng: &mut SplitMix64,
+    ) -> (u64, Vec<bool>) {
+        let n = efficiency_ordering.len();
+        let break_index = break_solution.break_index;
+
+        let mut included = vec![false; n];
+        included[..break_index].fill(true);
+        let mut weight = break_solution.total_weight;
+        let mut profit = break_solution.total_profit;
+
+        // The LP relaxation of the remaining items and capacity takes the items that fit entirely,
+        // and a fraction of the first one that doesn't
+        for pos in break_index..n {
+            let item = &input.items[efficiency_ordering[pos]];
+            let remaining_capacity = input.capacity - weight;
+            if item.weight > remaining_capacity {
+                let fraction = remaining_capacity as f64 / item.weight as f64;
+                if rng.next_f64() >= fraction {
+                    continue;
+                }
+
+                // Repair: drop the least efficient of the other included items until it fits
+                for drop_pos in (0..pos).rev() {
+                    if item.weight <= input.capacity - weight {
+                        break;
+                    }
+                    if included[drop_pos] {
+                        let dropped = &input.items[efficiency_ordering[drop_pos]];
+                        included[drop_pos] = false;
+                        weight -= dropped.weight;
+                        profit -= dropped.profit;
+                    }
+                }
+            }
+
+            included[pos] = true;
+            weight += item.weight;
+            profit += item.profit;
+        }
+
+        // Fill the capacity freed by the repairs greedily
+        for (pos, &item_idx) in efficiency_ordering.iter().enumerate() {
+            let item = &input.items[item_idx];
+            if !included[pos] && item.weight <= input.capacity - weight {
+                included[pos] = true;
+                weight += item.weight;
+                profit += item.profit;
+            }
+        }
+
+        (profit, included)
+    }
+}
+
+impl KnapsackSolver for RandomizedRoundingSolver {
+    fn solve(&self, input: &KnapsackInput) -> KnapsackSolution {
+        let (efficiency_ordering, mut decision_vec, base_profit) =
+            prepare_items(input, &efficiency_cmp);
+        // The break solution marks its items as well, but the trials may drop some of them
+        let mut break_decisions = decision_vec.clone();
+        let break_solution = BreakSolution::new(
+            input,
+            &efficiency_ordering,
+            break_decisions.as_mut_bitslice(),
+        );
+
+        let mut rng = SplitMix64(self.seed);
+        let mut best: Option<(u64, Vec<bool>)> = None;
+        for _ in 0..self.trials.max(1) {
+            let (profit, included) =
+                Self::round(input, &efficiency_ordering, &break_solution, &mut rng);
+
+            match &best {
+                Some((best_profit, _)) if *best_profit >= profit => {}
+                _ => best = Some((profit, included)),
+            }
+        }
+
+        let (profit, included) = best.unwrap();
+        for (pos, &item_idx) in efficiency_ordering.iter().enumerate() {
+            if included[pos] {
+                decision_vec.set(item_idx, true);
+            }
+        }
+
+        KnapsackSolution {
+            items: decision_vec.iter_ones().collect(),
+            total_value: profit + base_profit,
+        }
+    }
+
+    fn method(&self) -> KnapsackMethod {
+        KnapsackMethod::RandomizedRounding
+    }
+
+    fn capabilities(&self) -> SolverCapabilities {
+        SolverCapabilities::APPROXIMATE
+            | SolverCapabilities::ZERO_WEIGHT_ITEMS
+            | SolverCapabilities::SCALABLE
+    }
+}
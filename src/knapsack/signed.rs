@@ -0,0 +1,112 @@
+use alloc::vec::Vec;
+
+use super::{
+    bundle::bundle_members, KnapsackInput, KnapsackInputError, KnapsackItem, KnapsackSolver,
+};
+
+/// A problem in which the items may have a negative profit (e.g. items one must pay to carry)
+///
+/// A negative profit item is never worth selecting on its own, but it may be worth it when bundled
+/// with profitable items (see `solve_with_bundles`).
+#[derive(Debug, Clone)]
+pub struct SignedKnapsackInput {
+    weights: Vec<u64>,
+    profits: Vec<i64>,
+    capacity: u64,
+}
+
+/// A solution of a `SignedKnapsackInput`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SignedKnapsackSolution {
+    // index of items selected, sorted in ascending order
+    pub items: Vec<usize>,
+    pub total_value: i64,
+}
+
+impl SignedKnapsackInput {
+    /// Create an input where the i-th item has the weight `weights[i]` and the profit `profits[i]`
+    pub fn new(
+        weights: Vec<u64>,
+        profits: Vec<i64>,
+        capacity: u64,
+    ) -> Result<Self, KnapsackInputError> {
+        if weights.len() != profits.len() {
+            return Err(KnapsackInputError::ItemCountMismatch {
+                weights: weights.len(),
+                profits: profits.len(),
+            });
+        }
+        if capacity == 0 {
+            return Err(KnapsackInputError::ZeroCapacity);
+        }
+
+        Ok(Self {
+            weights,
+            profits,
+            capacity,
+        })
+    }
+
+    pub fn weights(&self) -> &[u64] {
+        &self.weights
+    }
+
+    pub fn profits(&self) -> &[i64] {
+        &self.profits
+    }
+
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// Solve the problem, never selecting the negative profit items
+    pub fn solve(&self, solver: &dyn KnapsackSolver) -> SignedKnapsackSolution {
+        self.solve_with_bundles(&[], solver)
+            .expect("no bundle, so no invalid item")
+    }
+
+    /// Solve the problem, requiring the items of each bundle to be selected together
+    ///
+    /// The bundles are merged as in `KnapsackInput::with_bundles`. Since the weights are never
+    /// negative, the composite items with a negative total profit are dropped, and the other ones
+    /// are given to the solver. Fails if an index is out of bounds.
+    pub fn solve_with_bundles(
+        &self,
+        bundles: &[Vec<usize>],
+        solver: &dyn KnapsackSolver,
+    ) -> Result<SignedKnapsackSolution, KnapsackInputError> {
+        let members: Vec<Vec<usize>> = bundle_members(self.weights.len(), bundles)?
+            .into_iter()
+            .filter(|group| group.iter().map(|&i| self.profits[i]).sum::<i64>() >= 0)
+            .collect();
+        if members.is_empty() {
+            return Ok(SignedKnapsackSolution::default());
+        }
+
+        let items = members
+            .iter()
+            .map(|group| {
+                let weight = group
+                    .iter()
+                    .fold(0u64, |total, &i| total.saturating_add(self.weights[i]));
+                // The total profit of the kept groups is never negative
+                let profit = group.iter().map(|&i| self.profits[i]).sum::<i64>() as u64;
+                KnapsackItem::new(weight, profit)
+            })
+            .collect();
+        let input = KnapsackInput::new(items, self.capacity, 1)?;
+
+        let mut items: Vec<usize> = solver
+            .solve(&input)
+            .items
+            .iter()
+            .flat_map(|&composite| members[composite].iter().copied())
+            .collect();
+        items.sort_unstable();
+
+        Ok(SignedKnapsackSolution {
+            total_value: items.iter().map(|&i| self.profits[i]).sum(),
+            items,
+        })
+    }
+}
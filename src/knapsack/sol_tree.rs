@@ -1,35 +1,25 @@
 // Using the SolutionFragment struct and a vector that stores the order in which the elements were
 // considered/visited, we can reconstruct the solution by traversing the tree from the last element
 
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+
 pub struct SolutionTree {
     fragments: Vec<SolutionFragment>,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Default)]
 pub struct SolutionFragment {
     /// The value of the fragment which represent a series of decisions bitwise encoded
     pub value: u64,
-    /// The index of the previous fragment in the tree
-    previous_idx: Option<usize>,
-}
-
-impl Default for SolutionFragment {
-    fn default() -> Self {
-        SolutionFragment {
-            value: 0,
-            previous_idx: None,
-        }
-    }
+    /// The index of the previous fragment in the tree, plus one
+    ///
+    /// The offset lets the option use the zero niche, so that it takes 8 bytes instead of 16,
+    /// which matters since every min-knap state holds a fragment.
+    previous_idx: Option<NonZeroUsize>,
 }
 
 impl SolutionFragment {
-    pub fn new(previous_idx: Option<usize>) -> Self {
-        SolutionFragment {
-            value: 0,
-            previous_idx,
-        }
-    }
-
     pub fn add_decision(&mut self, decision: bool) {
         self.value <<= 1;
         self.value |= decision as u64;
@@ -40,7 +30,7 @@ impl SolutionFragment {
     }
 
     pub fn update_previous_idx(&mut self, previous_idx: Option<usize>) {
-        self.previous_idx = previous_idx;
+        self.previous_idx = previous_idx.and_then(|idx| NonZeroUsize::new(idx + 1));
     }
 
     pub fn clear_value(&mut self) {
@@ -48,7 +38,7 @@ impl SolutionFragment {
     }
 
     pub fn get_previous_idx(&self) -> Option<usize> {
-        self.previous_idx
+        self.previous_idx.map(|idx| idx.get() - 1)
     }
 }
 
@@ -59,9 +49,9 @@ impl SolutionTree {
         }
     }
 
-    /// Pushes a new fragment to the tree and clears its content
-    /// The function also updates the "previous_idx" field of the fragment so that it points to the
-    /// pushed fragment
+    // Pushes a new fragment to the tree and clears its content
+    // The function also updates the "previous_idx" field of the fragment so that it points to the
+    // pushed fragment
     // pub fn push_and_clear(&mut self, fragment: &mut SolutionFragment) {
     //     fragment.previous_idx = Some(self.fragments.len());
     //     self.fragments.push(*fragment);
@@ -78,4 +68,14 @@ impl SolutionTree {
     pub fn get_fragment(&self, idx: usize) -> Option<&SolutionFragment> {
         self.fragments.get(idx)
     }
+
+    /// Reserves the memory for pushing `additional` fragments at once
+    pub fn reserve(&mut self, additional: usize) {
+        self.fragments.reserve(additional);
+    }
+
+    /// The memory reserved for the fragments, in bytes
+    pub fn reserved_bytes(&self) -> usize {
+        self.fragments.capacity() * core::mem::size_of::<SolutionFragment>()
+    }
 }
@@ -0,0 +1,53 @@
+use alloc::vec::Vec;
+
+use super::{KnapsackInput, KnapsackSolution};
+
+/// Kind of instance that can be solved without a solver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrivialCase {
+    /// There are no items
+    NoItems,
+    /// No item has a positive profit, so the empty solution is optimal
+    ZeroProfits,
+    /// All the items fit in the knapsack together
+    AllFit,
+    /// There is a single item, which is taken if it fits
+    SingleItem,
+}
+
+impl KnapsackInput {
+    /// Detects whether the instance is trivial, returning the first matching case in the order of
+    /// the `TrivialCase` variants
+    pub fn is_trivial(&self) -> Option<TrivialCase> {
+        if self.items.is_empty() {
+            Some(TrivialCase::NoItems)
+        } else if self.items.iter().all(|item| item.profit == 0) {
+            Some(TrivialCase::ZeroProfits)
+        } else if self.total_weight() <= self.capacity {
+            Some(TrivialCase::AllFit)
+        } else if self.items.len() == 1 {
+            Some(TrivialCase::SingleItem)
+        } else {
+            None
+        }
+    }
+
+    /// Solves the instance directly if it is trivial (see `is_trivial`)
+    ///
+    /// Only the items with a positive profit are taken, so the solution has the minimum weight
+    /// among the optimal ones, like the one of `DpSolver`. `None` is also returned if the total
+    /// profit of the solution overflows a `u64`.
+    pub fn solve_trivial(&self) -> Option<KnapsackSolution> {
+        let items: Vec<usize> = match self.is_trivial()? {
+            TrivialCase::NoItems | TrivialCase::ZeroProfits => Vec::new(),
+            TrivialCase::AllFit | TrivialCase::SingleItem => (0..self.items.len())
+                .filter(|&i| self.items[i].profit > 0 && self.items[i].weight <= self.capacity)
+                .collect(),
+        };
+        let total_value = items
+            .iter()
+            .try_fold(0u64, |total, &i| total.checked_add(self.items[i].profit))?;
+
+        Some(KnapsackSolution { items, total_value })
+    }
+}
@@ -0,0 +1,177 @@
+use alloc::vec::Vec;
+
+use super::{KnapsackInput, KnapsackInputError, KnapsackItem};
+
+/// Read access to the items and the capacity of a problem, so that the solvers can work both on
+/// owned inputs and on views without copying the items
+pub(crate) trait ItemSource {
+    fn item_count(&self) -> usize;
+
+    fn item(&self, index: usize) -> KnapsackItem;
+
+    fn capacity(&self) -> u64;
+
+    fn items(&self) -> impl Iterator<Item = KnapsackItem> + '_ {
+        (0..self.item_count()).map(|i| self.item(i))
+    }
+
+    /// Returns the total weight of the items, saturating at `u64::MAX`
+    fn total_weight(&self) -> u64 {
+        self.items()
+            .fold(0u64, |total, item| total.saturating_add(item.weight))
+    }
+
+    /// Returns the total profit of the items, or `None` if it overflows a `u64`
+    fn total_profit(&self) -> Option<u64> {
+        self.items()
+            .try_fold(0u64, |total, item| total.checked_add(item.profit))
+    }
+
+    /// Returns the weight of the heaviest item, or `None` if there are no items
+    fn max_weight(&self) -> Option<u64> {
+        self.items().map(|item| item.weight).max()
+    }
+
+    /// Returns the Dantzig upper bound of the problem (see `KnapsackInput::lp_bound`)
+    fn lp_bound(&self) -> u64 {
+        // Zero weight items are always taken entirely
+        let mut bound: u64 = self
+            .items()
+            .filter(|item| item.weight == 0)
+            .map(|item| item.profit)
+            .sum();
+
+        let mut items: Vec<KnapsackItem> = self.items().filter(|item| item.weight > 0).collect();
+        // Sort the items by efficiency in descending order, without using floats
+        items.sort_by(|a, b| {
+            let eff_a = a.profit as u128 * b.weight as u128;
+            let eff_b = b.profit as u128 * a.weight as u128;
+            eff_b.cmp(&eff_a)
+        });
+
+        let mut remaining_capacity = self.capacity();
+
+        for item in items {
+            if item.weight <= remaining_capacity {
+                remaining_capacity -= item.weight;
+                bound += item.profit;
+            } else {
+                bound +=
+                    (remaining_capacity as u128 * item.profit as u128 / item.weight as u128) as u64;
+                break;
+            }
+        }
+
+        bound
+    }
+}
+
+impl ItemSource for KnapsackInput {
+    fn item_count(&self) -> usize {
+        self.items.len()
+    }
+
+    fn item(&self, index: usize) -> KnapsackItem {
+        self.items[index]
+    }
+
+    fn capacity(&self) -> u64 {
+        self.capacity
+    }
+}
+
+impl ItemSource for KnapsackView<'_> {
+    fn item_count(&self) -> usize {
+        self.weights.len()
+    }
+
+    fn item(&self, index: usize) -> KnapsackItem {
+        KnapsackItem::new(self.weights[index], self.profits[index])
+    }
+
+    fn capacity(&self) -> u64 {
+        self.capacity
+    }
+}
+
+/// Borrowed view of a knapsack problem stored in a columnar layout, with the weights and the
+/// profits of the items in separate slices
+///
+/// The view doesn't own the item data. Use `KnapsackSolver::solve_view` to solve it: `DpSolver` and
+/// `MinKnapSolver` work on the slices directly, while the other solvers copy the items first.
+#[derive(Debug, Clone, Copy)]
+pub struct KnapsackView<'a> {
+    weights: &'a [u64],
+    profits: &'a [u64],
+    capacity: u64,
+}
+
+impl<'a> KnapsackView<'a> {
+    /// Create a view over the given slices, where the i-th item has the weight `weights[i]` and
+    /// the profit `profits[i]`
+    pub fn new(
+        weights: &'a [u64],
+        profits: &'a [u64],
+        capacity: u64,
+    ) -> Result<Self, KnapsackInputError> {
+        if weights.len() != profits.len() {
+            return Err(KnapsackInputError::ItemCountMismatch {
+                weights: weights.len(),
+                profits: profits.len(),
+            });
+        }
+        if capacity == 0 {
+            return Err(KnapsackInputError::ZeroCapacity);
+        }
+
+        Ok(Self {
+            weights,
+            profits,
+            capacity,
+        })
+    }
+
+    pub fn weights(&self) -> &'a [u64] {
+        self.weights
+    }
+
+    pub fn profits(&self) -> &'a [u64] {
+        self.profits
+    }
+
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Copy the viewed items into an owned input, for the solvers that work on `KnapsackInput`
+    pub fn to_input(&self) -> KnapsackInput {
+        let items = self
+            .weights
+            .iter()
+            .zip(self.profits)
+            .map(|(&weight, &profit)| KnapsackItem::new(weight, profit))
+            .collect();
+
+        KnapsackInput {
+            items,
+            capacity: self.capacity,
+            granularity: 1,
+            profit_scale: 1,
+            labels: Vec::new(),
+        }
+    }
+}
+
+impl From<KnapsackView<'_>> for KnapsackInput {
+    fn from(view: KnapsackView<'_>) -> Self {
+        view.to_input()
+    }
+}
@@ -0,0 +1,108 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use bitvec::prelude::*;
+
+use super::{dp::DpSolver, KnapsackInputError, KnapsackSolution};
+
+/// A problem whose weights and capacity don't fit in a u64
+///
+/// The profits are still u64 values, since the DP table is indexed by the profit. Use
+/// `DpSolver::solve_wide` to solve it.
+#[derive(Debug, Clone)]
+pub struct WideKnapsackInput {
+    weights: Vec<u128>,
+    profits: Vec<u64>,
+    capacity: u128,
+}
+
+impl WideKnapsackInput {
+    /// Create an input where the i-th item has the weight `weights[i]` and the profit `profits[i]`
+    pub fn new(
+        weights: Vec<u128>,
+        profits: Vec<u64>,
+        capacity: u128,
+    ) -> Result<Self, KnapsackInputError> {
+        if weights.len() != profits.len() {
+            return Err(KnapsackInputError::ItemCountMismatch {
+                weights: weights.len(),
+                profits: profits.len(),
+            });
+        }
+        if capacity == 0 {
+            return Err(KnapsackInputError::ZeroCapacity);
+        }
+
+        Ok(Self {
+            weights,
+            profits,
+            capacity,
+        })
+    }
+
+    pub fn weights(&self) -> &[u128] {
+        &self.weights
+    }
+
+    pub fn profits(&self) -> &[u64] {
+        &self.profits
+    }
+
+    pub fn capacity(&self) -> u128 {
+        self.capacity
+    }
+}
+
+impl DpSolver {
+    /// Solve a problem with u128 weights, using a profit indexed DP
+    ///
+    /// Only the last row of the table is kept, along with one bit per cell telling whether the
+    /// item improved the weight of the profit, for recovering the items. The weights above the
+    /// capacity are never stored, so the sums can't overflow. As with `solve`, the returned
+    /// solution has the minimum total weight among the optimal ones.
+    pub fn solve_wide(&self, input: &WideKnapsackInput) -> KnapsackSolution {
+        let max_profit: usize = input.profits.iter().map(|&profit| profit as usize).sum();
+        let row_len = max_profit + 1;
+
+        // The minimum weight of each profit, None for the profits that can't be reached within
+        // the capacity
+        let mut row: Vec<Option<u128>> = vec![None; row_len];
+        row[0] = Some(0);
+        let mut improved = bitvec![0; input.weights.len() * row_len];
+
+        for (i, (&item_weight, &item_profit)) in
+            input.weights.iter().zip(&input.profits).enumerate()
+        {
+            let item_profit = item_profit as usize;
+            for profit in (item_profit.max(1)..row_len).rev() {
+                let weight = row[profit - item_profit]
+                    .and_then(|weight| weight.checked_add(item_weight))
+                    .filter(|&weight| weight <= input.capacity);
+
+                if let Some(weight) = weight {
+                    if row[profit].is_none_or(|current| weight < current) {
+                        row[profit] = Some(weight);
+                        improved.set(i * row_len + profit, true);
+                    }
+                }
+            }
+        }
+
+        let total_value = row.iter().rposition(Option::is_some).unwrap();
+
+        let mut items = Vec::new();
+        let mut profit = total_value;
+        for i in (0..input.weights.len()).rev() {
+            if profit > 0 && improved[i * row_len + profit] {
+                items.push(i);
+                profit -= input.profits[i] as usize;
+            }
+        }
+        items.reverse();
+
+        KnapsackSolution {
+            items,
+            total_value: total_value as u64,
+        }
+    }
+}
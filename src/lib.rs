@@ -1,7 +1,49 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+/// Logs a debug message through the `log` crate, only when the `log` feature is enabled
+macro_rules! log_debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "log")]
+        log::debug!($($arg)*);
+    };
+}
+
+/// Logs a trace message through the `log` crate, only when the `log` feature is enabled
+macro_rules! log_trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "log")]
+        log::trace!($($arg)*);
+    };
+}
+
 pub mod knapsack;
 
+#[cfg(feature = "std")]
+pub use knapsack::analysis::InstanceStats;
+pub use knapsack::balknap::BalKnapSolver;
 pub use knapsack::bkt::BktSolver;
-pub use knapsack::dp::DpSolver;
-pub use knapsack::fptas::FptasDpSolver;
-pub use knapsack::minknap::MinKnapSolver;
+#[cfg(feature = "tokio")]
+pub use knapsack::blocking::solve_async;
+pub use knapsack::bundle::BundledInput;
+pub use knapsack::category::CategorizedInput;
+pub use knapsack::compare::{compare_solvers, ComparisonReport, SolverOutcome};
+pub use knapsack::dp::{DpSolver, DpTable, SolveObjective};
+pub use knapsack::equal_weight::EqualWeightSolver;
+pub use knapsack::exclusion::ExclusiveInput;
+pub use knapsack::forced::ForcedInput;
+pub use knapsack::fptas::{FptasDpSolver, FptasScratch};
+pub use knapsack::grouping::GroupedInput;
+pub use knapsack::iddfs::IddfsBktSolver;
+pub use knapsack::minknap::{CachedMinKnapSolver, MinKnapSolver};
+pub use knapsack::multidim::{
+    LpBound, MultiDimBnbSolver, MultiDimItem, MultiDimKnapsackInput, UpperBound,
+};
+pub use knapsack::multiple::{MultiKnapsackSolution, MultipleKnapsackSolver};
+pub use knapsack::rounding::RandomizedRoundingSolver;
+pub use knapsack::signed::{SignedKnapsackInput, SignedKnapsackSolution};
+pub use knapsack::trivial::TrivialCase;
+pub use knapsack::view::KnapsackView;
+pub use knapsack::wide::WideKnapsackInput;
 pub use knapsack::*;
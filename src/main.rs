@@ -1,22 +1,69 @@
 mod benchmark;
+#[cfg(feature = "mem-profile")]
+mod mem_profile;
 
-use benchmark::run_benchmark;
-use clap::{Parser, ValueEnum};
+use anyhow::{anyhow, Context};
+use benchmark::{run_benchmark, run_benchmarks_parallel};
+use clap::{CommandFactory, Parser, ValueEnum};
 use knapsack::{
-    BktSolver, DpSolver, FptasDpSolver, KnapsackInput, KnapsackMethod, KnapsackSolver,
-    MinKnapSolver,
+    BalKnapSolver, BktSolver, ColumnOrder, DpSolver, EqualWeightSolver, FptasDpSolver,
+    IddfsBktSolver, KnapsackInput, KnapsackInputError, KnapsackMethod, KnapsackSolver,
+    MinKnapSolver, RandomizedRoundingSolver, SolverCapabilities,
 };
 use lazy_static::lazy_static;
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
-use std::io::BufReader;
-use std::path::PathBuf;
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Parser)]
 #[clap(name = "knapsack", version = "0.1.0", author = "Albert24GG")]
 struct CommandArgs {
-    #[arg(short, long, value_name = "TEST_FILE", value_hint = clap::ValueHint::FilePath)]
-    input_file: PathBuf,
+    #[arg(short, long, value_name = "TEST_FILE", value_hint = clap::ValueHint::FilePath, required_unless_present = "weights_file")]
+    input_file: Option<PathBuf>,
+
+    #[arg(long, value_name = "WEIGHTS_FILE", value_hint = clap::ValueHint::FilePath, conflicts_with = "input_file", requires_all = ["profits_file", "capacity"])]
+    /// File with one item weight per line (alternative to --input-file)
+    weights_file: Option<PathBuf>,
+
+    #[arg(long, value_name = "PROFITS_FILE", value_hint = clap::ValueHint::FilePath, requires = "weights_file")]
+    /// File with one item profit per line (used with --weights-file)
+    profits_file: Option<PathBuf>,
+
+    #[arg(long, requires = "input_file")]
+    /// Solve every instance of an input file containing several concatenated instances
+    many: bool,
+
+    #[arg(long, value_enum, default_value_t = InputFormatCmd::Standard, requires = "input_file", conflicts_with = "many")]
+    /// Format of the input file: the standard one has the item count and the capacity on separate
+    /// lines, while the OR-Library one has them on the first line. The binary one is the packed
+    /// layout of little-endian u64 values (count, capacity, then weight and profit of each item)
+    format: InputFormatCmd,
+
+    #[arg(long, value_enum, default_value_t = ColumnOrderCmd::ValueWeight, requires = "input_file")]
+    /// Order of the numbers on the item lines of the input file
+    column_order: ColumnOrderCmd,
+
+    #[arg(short, long)]
+    /// Capacity of the knapsack. This is required with --weights-file, and overrides the capacity
+    /// of every instance of the input file otherwise
+    capacity: Option<u64>,
+
+    #[arg(short, long)]
+    /// Include the capacity utilization of the solution in the output of the run action
+    verbose: bool,
+
+    #[arg(long)]
+    /// Check that the solution of the run action is feasible and that its value matches its items
+    verify: bool,
+
+    #[arg(long)]
+    /// Embed the weight and the profit of each selected item in the output of the run action,
+    /// along with the total weight
+    detailed: bool,
 
     #[arg(short, long, value_name = "OUTPUT_FILE", value_hint = clap::ValueHint::FilePath, default_value = "out.json")]
     output_file: PathBuf,
@@ -25,13 +72,31 @@ struct CommandArgs {
     /// Granularity for the FPTAS method. This is only used when the method is FPTAS.
     granularity: u32,
 
+    #[arg(long, value_parser = clap::value_parser!(u64).range(10..))]
+    /// Number of benchmark samples (at least 10)
+    bench_samples: Option<u64>,
+
+    #[arg(long)]
+    /// Benchmark measurement time, in seconds
+    bench_time_secs: Option<u64>,
+
+    #[arg(long)]
+    /// Include the raw measurements of every benchmark sample in the output
+    bench_raw_samples: bool,
+
     #[arg()]
     /// Action to perform
     action: KnapsackAction,
 
-    #[arg()]
+    #[arg(long, conflicts_with = "method")]
+    /// Benchmark every method that isn't exponential in parallel instead of a single one. The
+    /// exponential methods (bkt, iddfs-bkt) would compete for the CPU for much longer than the
+    /// others, so they are only benchmarked on their own
+    all: bool,
+
+    #[arg(required_unless_present = "all")]
     /// Method used for solving the problem
-    method: KnapsackMethodCmd,
+    method: Option<KnapsackMethodCmd>,
 }
 
 #[derive(Clone, Copy, Debug, ValueEnum)]
@@ -40,14 +105,36 @@ enum KnapsackAction {
     Benchmark,
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum InputFormatCmd {
+    Standard,
+    OrLibrary,
+    Binary,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ColumnOrderCmd {
+    ValueWeight,
+    WeightValue,
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum, Hash, PartialEq, Eq)]
 enum KnapsackMethodCmd {
     Dp,
     Bkt,
     Fptas,
     MinKnap,
+    EqualWeight,
+    IddfsBkt,
+    BalKnap,
+    RandomizedRounding,
 }
 
+const RANDOMIZED_ROUNDING_SOLVER: RandomizedRoundingSolver = RandomizedRoundingSolver::new(
+    RandomizedRoundingSolver::DEFAULT_TRIALS,
+    RandomizedRoundingSolver::DEFAULT_SEED,
+);
+
 lazy_static! {
     static ref METHOD_MAPPER: HashMap<KnapsackMethod, &'static dyn KnapsackSolver> = {
         let mut m = HashMap::new();
@@ -58,61 +145,251 @@ lazy_static! {
             KnapsackMethod::MinKnap,
             &MinKnapSolver as &dyn KnapsackSolver,
         );
+        m.insert(
+            KnapsackMethod::EqualWeight,
+            &EqualWeightSolver as &dyn KnapsackSolver,
+        );
+        m.insert(
+            KnapsackMethod::IddfsBkt,
+            &IddfsBktSolver as &dyn KnapsackSolver,
+        );
+        m.insert(
+            KnapsackMethod::BalKnap,
+            &BalKnapSolver as &dyn KnapsackSolver,
+        );
+        m.insert(
+            KnapsackMethod::RandomizedRounding,
+            &RANDOMIZED_ROUNDING_SOLVER as &dyn KnapsackSolver,
+        );
         m
     };
 }
 
-fn parse_input(args: &CommandArgs) -> KnapsackInput {
-    let file = File::open(&args.input_file).unwrap();
+fn open_file(path: &Path, description: &str) -> anyhow::Result<BufReader<File>> {
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open the {} \"{}\"", description, path.display()))?;
+    Ok(BufReader::new(file))
+}
+
+fn parse_input(args: &CommandArgs) -> anyhow::Result<Vec<KnapsackInput>> {
+    let mut parsed_inputs = match (&args.input_file, &args.weights_file, &args.profits_file) {
+        (Some(input_file), _, _) => {
+            let reader = open_file(input_file, "input file")?;
+            let error_context = || format!("Invalid input file \"{}\"", input_file.display());
 
-    let reader = BufReader::new(file);
+            let order = match args.column_order {
+                ColumnOrderCmd::ValueWeight => ColumnOrder::ValueWeight,
+                ColumnOrderCmd::WeightValue => ColumnOrder::WeightValue,
+            };
 
-    let mut parsed_input = KnapsackInput::parse_input(reader).unwrap();
-    parsed_input.set_granularity(args.granularity).unwrap();
+            let parsed_input = match args.format {
+                InputFormatCmd::Standard if args.many => {
+                    KnapsackInput::parse_many_with_order(reader, order)
+                }
+                InputFormatCmd::Standard => {
+                    KnapsackInput::parse_input_with_order(reader, order).map(|input| vec![input])
+                }
+                InputFormatCmd::OrLibrary => {
+                    KnapsackInput::parse_or_library_with_order(reader, order)
+                        .map(|input| vec![input])
+                }
+                InputFormatCmd::Binary => {
+                    KnapsackInput::parse_binary(reader).map(|input| vec![input])
+                }
+            };
+            parsed_input.with_context(error_context)?
+        }
+        (None, Some(weights_file), Some(profits_file)) => {
+            let weights = open_file(weights_file, "weights file")?;
+            let profits = open_file(profits_file, "profits file")?;
+
+            // clap guarantees that the capacity is provided with the weights file
+            let capacity = args.capacity.unwrap();
+            vec![KnapsackInput::parse_split(weights, profits, capacity)
+                .context("Invalid weights or profits file")?]
+        }
+        _ => unreachable!("clap guarantees that an input source is provided"),
+    };
+    for parsed_input in parsed_inputs.iter_mut() {
+        parsed_input
+            .set_granularity(args.granularity)
+            .context("Invalid granularity")?;
+        if let Some(capacity) = args.capacity {
+            parsed_input
+                .set_capacity(capacity)
+                .context("Invalid capacity")?;
+        }
+    }
 
-    parsed_input
+    Ok(parsed_inputs)
 }
 
 fn get_method(method: &KnapsackMethodCmd) -> KnapsackMethod {
     match method {
         KnapsackMethodCmd::Dp => KnapsackMethod::Dp,
         KnapsackMethodCmd::Bkt => KnapsackMethod::Bkt,
-        KnapsackMethodCmd::Fptas { .. } => KnapsackMethod::Fptas,
+        KnapsackMethodCmd::Fptas => KnapsackMethod::Fptas,
         KnapsackMethodCmd::MinKnap => KnapsackMethod::MinKnap,
+        KnapsackMethodCmd::EqualWeight => KnapsackMethod::EqualWeight,
+        KnapsackMethodCmd::IddfsBkt => KnapsackMethod::IddfsBkt,
+        KnapsackMethodCmd::BalKnap => KnapsackMethod::BalKnap,
+        KnapsackMethodCmd::RandomizedRounding => KnapsackMethod::RandomizedRounding,
     }
 }
 
-fn get_solver(method: &KnapsackMethodCmd) -> Option<&dyn KnapsackSolver> {
+fn get_solver(method: &KnapsackMethodCmd) -> anyhow::Result<&dyn KnapsackSolver> {
     let method = get_method(method);
-    METHOD_MAPPER.get(&method).copied()
+    METHOD_MAPPER
+        .get(&method)
+        .copied()
+        .ok_or_else(|| anyhow!("No solver is available for the {:?} method", method))
 }
 
-fn main() {
-    let args = CommandArgs::parse();
-
-    let input = parse_input(&args);
-    let solver = get_solver(&args.method).unwrap();
+/// Exit code for an input that can't be parsed or is invalid
+const INVALID_INPUT_EXIT_CODE: u8 = 3;
+/// Exit code for a file that can't be read or written
+const IO_ERROR_EXIT_CODE: u8 = 4;
 
-    let output = match args.action {
-        KnapsackAction::Run => {
-            let solution = solver.solve(&input);
-            serde_json::to_value(&solution).unwrap()
+/// Returns the exit code for the given error (see the exit code table in the README)
+fn exit_code(error: &anyhow::Error) -> u8 {
+    for cause in error.chain() {
+        if let Some(input_error) = cause.downcast_ref::<KnapsackInputError>() {
+            return match input_error {
+                KnapsackInputError::ReadError(_) => IO_ERROR_EXIT_CODE,
+                _ => INVALID_INPUT_EXIT_CODE,
+            };
         }
-        KnapsackAction::Benchmark => {
-            let result = run_benchmark(solver, &input).unwrap();
-            serde_json::to_value(&result).unwrap()
+        if cause.is::<io::Error>() {
+            return IO_ERROR_EXIT_CODE;
         }
-    };
+        // serde_json wraps the errors of the writer it serializes into
+        if let Some(json_error) = cause.downcast_ref::<serde_json::Error>() {
+            if json_error.is_io() {
+                return IO_ERROR_EXIT_CODE;
+            }
+        }
+    }
+    1
+}
 
-    let file = File::create(args.output_file.clone()).ok();
-    match file {
-        Some(file) => serde_json::to_writer(file, &output).unwrap(),
-        None => {
-            println!(
-                "Failed to write output to \"{}\":\n {}",
-                args.output_file.display(),
-                output
-            )
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {:?}", error);
+            ExitCode::from(exit_code(&error))
         }
     }
 }
+
+fn run() -> anyhow::Result<()> {
+    let args = CommandArgs::parse();
+    if args.all && matches!(args.action, KnapsackAction::Run) {
+        CommandArgs::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "--all can only be used with the benchmark action",
+            )
+            .exit();
+    }
+
+    let inputs = parse_input(&args)?;
+    let sample_size = args.bench_samples.map(|samples| samples as usize);
+    let measurement_time = args.bench_time_secs.map(Duration::from_secs);
+
+    let mut outputs: Vec<Value> = inputs
+        .iter()
+        .map(|input| -> anyhow::Result<Value> {
+            let output = match (args.action, &args.method) {
+                (KnapsackAction::Benchmark, None) => {
+                    let solvers: Vec<&dyn KnapsackSolver> = KnapsackMethod::all()
+                        .iter()
+                        .map(|method| METHOD_MAPPER[method])
+                        .filter(|solver| {
+                            solver.capabilities().contains(SolverCapabilities::SCALABLE)
+                        })
+                        .collect();
+                    let results = run_benchmarks_parallel(
+                        &solvers,
+                        input,
+                        sample_size,
+                        measurement_time,
+                        args.bench_raw_samples,
+                    )
+                    .context("Failed to run the benchmarks")?;
+
+                    // The results are keyed by the method name
+                    let results: BTreeMap<&str, _> = results
+                        .iter()
+                        .map(|(method, result)| (method.as_ref(), result))
+                        .collect();
+                    serde_json::to_value(&results)?
+                }
+                (KnapsackAction::Run, Some(method)) => {
+                    let solver = get_solver(method)?;
+                    // Only the solve itself is timed, without the parsing and the serialization.
+                    // The trivial instances are solved directly, without running the solver
+                    let start = Instant::now();
+                    let solution = match input.solve_trivial() {
+                        Some(solution) => solution,
+                        None => solver.try_solve(input)?,
+                    };
+                    let solve_time = start.elapsed();
+
+                    if args.verify {
+                        solution
+                            .verify(input)
+                            .with_context(|| format!("Invalid solution of {}", solver.name()))?;
+                    }
+
+                    let mut output = if args.detailed {
+                        serde_json::to_value(solution.detailed(input))?
+                    } else {
+                        serde_json::to_value(&solution)?
+                    };
+                    output["method"] = Value::from(solver.name());
+                    output["solve_time_ns"] = Value::from(solve_time.as_nanos() as u64);
+                    if args.verbose {
+                        output["utilization"] = Value::from(solution.utilization(input));
+                    }
+                    output
+                }
+                (KnapsackAction::Benchmark, Some(method)) => {
+                    let solver = get_solver(method)?;
+                    let result = run_benchmark(
+                        solver,
+                        input,
+                        sample_size,
+                        measurement_time,
+                        args.bench_raw_samples,
+                    )
+                    .context("Failed to run the benchmark")?;
+
+                    let mut output = serde_json::to_value(&result)?;
+                    output["method"] = Value::from(solver.name());
+                    output
+                }
+                (KnapsackAction::Run, None) => unreachable!("--all is only used for benchmarks"),
+            };
+            Ok(output)
+        })
+        .collect::<anyhow::Result<_>>()?;
+
+    // Several instances produce an array of results, one per instance
+    let output = if args.many {
+        Value::Array(outputs)
+    } else {
+        outputs.remove(0)
+    };
+
+    let output_context = || {
+        format!(
+            "Failed to write the output to \"{}\"",
+            args.output_file.display()
+        )
+    };
+    let file = File::create(&args.output_file).with_context(output_context)?;
+    serde_json::to_writer(file, &output).with_context(output_context)?;
+
+    Ok(())
+}
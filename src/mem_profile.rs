@@ -0,0 +1,70 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Wrapper of the system allocator counting the bytes allocated by each thread, and their peak
+///
+/// The counters are thread local, so that the benchmarks running in parallel don't see each
+/// other's allocations.
+struct TrackingAllocator;
+
+thread_local! {
+    static ALLOCATED: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+}
+
+fn record(delta: isize) {
+    // The counters may already be destroyed while the thread exits
+    let _ = ALLOCATED.try_with(|allocated| {
+        let current = allocated.get() + delta;
+        allocated.set(current);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(current)));
+    });
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(-(layout.size() as isize));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size as isize - layout.size() as isize);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static GLOBAL: TrackingAllocator = TrackingAllocator;
+
+/// Run the function, returning its result and the peak number of bytes it allocated on the
+/// current thread (including the result itself)
+pub fn measure_peak<R>(f: impl FnOnce() -> R) -> (R, u64) {
+    let baseline = ALLOCATED.with(Cell::get);
+    PEAK.with(|peak| peak.set(baseline));
+
+    let result = f();
+
+    let peak = PEAK.with(Cell::get);
+    (result, (peak - baseline) as u64)
+}
@@ -0,0 +1,15 @@
+//! Solving without the standard library features, as on an embedded target (run with
+//! `cargo test --no-default-features`)
+
+use knapsack::{DpSolver, KnapsackInput, KnapsackSolver, MinKnapSolver};
+
+#[test]
+fn solve_from_slices() {
+    let input = KnapsackInput::from_slices(&[3, 4, 5], &[4, 5, 6], 7).unwrap();
+
+    for solver in [&DpSolver as &dyn KnapsackSolver, &MinKnapSolver] {
+        let solution = solver.solve(&input);
+        assert_eq!(solution.items, vec![0, 1], "{}", solver.name());
+        assert_eq!(solution.total_value, 9, "{}", solver.name());
+    }
+}
@@ -0,0 +1,47 @@
+//! Statistics and analysis of the instances
+
+use knapsack::{KnapsackInput, KnapsackItem};
+
+/// Builds an input from (weight, profit) pairs
+fn input(items: &[(u64, u64)], capacity: u64) -> KnapsackInput {
+    let items = items
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    KnapsackInput::new(items, capacity, 1).unwrap()
+}
+
+/// Asserts that two floating point values are equal up to rounding errors
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-9,
+        "{} != {}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn statistics_of_a_known_instance() {
+    // the profits decrease as the weights increase, so the lightest item dominates the others
+    let stats = input(&[(1, 9), (2, 7), (3, 5), (6, 3)], 6).analyze();
+
+    assert_eq!(stats.item_count, 4);
+    assert_eq!(stats.capacity, 6);
+    assert_eq!((stats.min_weight, stats.max_weight), (1, 6));
+    assert_eq!((stats.min_profit, stats.max_profit), (3, 9));
+    assert_close(stats.mean_weight, 3.0);
+    assert_close(stats.mean_profit, 6.0);
+    // the weight deviations are (-2, -1, 0, 3) and the profit deviations (3, 1, -1, -3)
+    assert_close(stats.correlation, -16.0 / (14.0f64 * 20.0).sqrt());
+    assert_close(stats.capacity_ratio, 0.5);
+    assert_eq!(stats.dominated_count, 3);
+}
+
+#[test]
+fn statistics_of_a_perfectly_correlated_instance() {
+    let stats = input(&[(1, 2), (2, 4), (3, 6)], 3).analyze();
+
+    assert_close(stats.correlation, 1.0);
+    assert_eq!(stats.dominated_count, 0);
+}
@@ -0,0 +1,179 @@
+//! Benchmarks of several methods in parallel
+
+#[allow(dead_code)]
+#[path = "../src/benchmark.rs"]
+mod benchmark;
+#[cfg(feature = "mem-profile")]
+#[path = "../src/mem_profile.rs"]
+mod mem_profile;
+
+use benchmark::{run_benchmark, run_benchmarks_parallel};
+use knapsack::{
+    DpSolver, FptasDpSolver, KnapsackInput, KnapsackItem, KnapsackMethod, KnapsackSolver,
+    MinKnapSolver,
+};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// A small instance, solved in a fraction of a microsecond by every method
+fn small_input() -> KnapsackInput {
+    let items = [(3, 4), (4, 5), (5, 6), (2, 3)]
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    KnapsackInput::new(items, 9, 1).unwrap()
+}
+
+/// The point estimate and the confidence interval of an estimation, in nanoseconds
+fn estimation(result: &Value, metric: &str) -> (u64, u64, u64) {
+    let estimation = &result[metric];
+    let interval = &estimation["confidence_interval"];
+    (
+        interval["lower_bound"].as_u64().unwrap(),
+        estimation["point_estimate"].as_u64().unwrap(),
+        interval["upper_bound"].as_u64().unwrap(),
+    )
+}
+
+#[test]
+fn one_result_per_method() {
+    let input = small_input();
+    let solvers: [&dyn KnapsackSolver; 3] = [&DpSolver, &MinKnapSolver, &FptasDpSolver];
+
+    let results = run_benchmarks_parallel(
+        &solvers,
+        &input,
+        Some(10),
+        Some(Duration::from_millis(100)),
+        false,
+    )
+    .unwrap();
+
+    let methods: HashSet<KnapsackMethod> = results.keys().copied().collect();
+    assert_eq!(results.len(), solvers.len());
+    assert_eq!(
+        methods,
+        HashSet::from([
+            KnapsackMethod::Dp,
+            KnapsackMethod::MinKnap,
+            KnapsackMethod::Fptas
+        ])
+    );
+}
+
+#[test]
+fn smallest_sample_size_gives_a_valid_result() {
+    let result = run_benchmark(
+        &DpSolver,
+        &small_input(),
+        Some(10),
+        Some(Duration::from_millis(10)),
+        false,
+    )
+    .unwrap();
+    let result = serde_json::to_value(&result).unwrap();
+
+    for metric in ["mean", "median"] {
+        let (lower_bound, point_estimate, upper_bound) = estimation(&result, metric);
+        assert!(point_estimate > 0, "{}", metric);
+        assert!(
+            lower_bound <= point_estimate && point_estimate <= upper_bound,
+            "{}",
+            metric
+        );
+    }
+    assert!(result.get("samples").is_none());
+}
+
+#[test]
+fn trivial_one_item_instance_gives_positive_estimates() {
+    // the item always fits, so the solve takes only a few nanoseconds
+    let trivial = KnapsackInput::new(vec![KnapsackItem::new(1, 1)], 1, 1).unwrap();
+    let result = run_benchmark(
+        &MinKnapSolver,
+        &trivial,
+        Some(10),
+        Some(Duration::from_millis(10)),
+        false,
+    )
+    .unwrap();
+    let result = serde_json::to_value(&result).unwrap();
+
+    for metric in ["mean", "median"] {
+        let (lower_bound, point_estimate, upper_bound) = estimation(&result, metric);
+        assert!(lower_bound >= 1, "{}", metric);
+        assert!(point_estimate <= upper_bound, "{}", metric);
+    }
+}
+
+#[test]
+fn consecutive_benchmarks_are_independent() {
+    let input = small_input();
+    let mut sample_counts = Vec::new();
+
+    for sample_size in [10, 20] {
+        let result = run_benchmark(
+            &DpSolver,
+            &input,
+            Some(sample_size),
+            Some(Duration::from_millis(10)),
+            true,
+        )
+        .unwrap();
+        let result = serde_json::to_value(&result).unwrap();
+        sample_counts.push(result["samples"]["times"].as_array().unwrap().len());
+    }
+
+    // a stale result of the first run would report 10 samples for the second one
+    assert_eq!(sample_counts, vec![10, 20]);
+}
+
+#[cfg(feature = "mem-profile")]
+#[test]
+fn dp_peak_memory_is_larger_than_the_greedy_one() {
+    let items = (1..=100)
+        .map(|i| KnapsackItem::new(i % 17 + 1, i % 89 + 1))
+        .collect();
+    let input = KnapsackInput::new(items, 300, 1).unwrap();
+
+    let result = run_benchmark(
+        &DpSolver,
+        &input,
+        Some(10),
+        Some(Duration::from_millis(10)),
+        false,
+    )
+    .unwrap();
+    let dp_peak = serde_json::to_value(&result).unwrap()["peak_bytes"]
+        .as_u64()
+        .unwrap();
+    let (_, greedy_peak) = mem_profile::measure_peak(|| {
+        let mut solution = knapsack::KnapsackSolution::default();
+        solution.greedy_fill(&input);
+        solution
+    });
+
+    // the table has 100 rows and more than 4000 columns of u64 values
+    assert!(dp_peak > 100 * 4000 * 8, "{}", dp_peak);
+    assert!(dp_peak > greedy_peak, "{} <= {}", dp_peak, greedy_peak);
+}
+
+#[test]
+fn raw_samples_are_included_when_requested() {
+    let result = run_benchmark(
+        &MinKnapSolver,
+        &small_input(),
+        Some(10),
+        Some(Duration::from_millis(10)),
+        true,
+    )
+    .unwrap();
+    let result = serde_json::to_value(&result).unwrap();
+
+    let iters = result["samples"]["iters"].as_array().unwrap();
+    let times = result["samples"]["times"].as_array().unwrap();
+    assert!(!times.is_empty());
+    assert_eq!(iters.len(), times.len());
+    assert!(times.iter().all(|time| time.as_f64().unwrap() > 0.0));
+}
@@ -0,0 +1,59 @@
+//! Items that must be selected together
+
+use knapsack::{
+    DpSolver, KnapsackInput, KnapsackInputError, KnapsackItem, KnapsackSolver, SignedKnapsackInput,
+};
+
+/// Builds an input from (weight, profit) pairs
+fn input(items: &[(u64, u64)], capacity: u64) -> KnapsackInput {
+    let items = items
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    KnapsackInput::new(items, capacity, 1).unwrap()
+}
+
+#[test]
+fn bundling_changes_the_optimum() {
+    let input = input(&[(5, 10), (5, 10), (6, 1)], 10);
+    assert_eq!(DpSolver.solve(&input).items, vec![0, 1]);
+
+    // The bundle of the items 0 and 2 is too heavy, so only the item 1 is left
+    let bundled = input.with_bundles(&[vec![0, 2]]).unwrap();
+    let solution = bundled.solve(&DpSolver);
+
+    assert_eq!(solution.items, vec![1]);
+    assert_eq!(solution.total_value, 10);
+}
+
+#[test]
+fn bundles_sharing_an_item_are_merged() {
+    let input = input(&[(1, 1), (1, 1), (1, 1), (1, 1)], 10);
+    let bundled = input.with_bundles(&[vec![0, 2], vec![2, 3]]).unwrap();
+
+    assert_eq!(bundled.members(), &[vec![0, 2, 3], vec![1]]);
+}
+
+#[test]
+fn overflowing_bundle_is_rejected() {
+    let input = input(&[(1, 1), (1, u64::MAX), (2, u64::MAX)], 10);
+
+    assert!(matches!(
+        input.with_bundles(&[vec![1, 2]]),
+        Err(KnapsackInputError::BundleOverflow { index: 1 })
+    ));
+}
+
+#[test]
+fn negative_profit_item_is_only_selected_when_bundled() {
+    // every item fits, but the item 0 must be paid for
+    let input = SignedKnapsackInput::new(vec![2, 3, 4], vec![-5, 10, 3], 9).unwrap();
+
+    let solution = input.solve(&DpSolver);
+    assert_eq!(solution.items, vec![1, 2]);
+    assert_eq!(solution.total_value, 13);
+
+    let solution = input.solve_with_bundles(&[vec![0, 1]], &DpSolver).unwrap();
+    assert_eq!(solution.items, vec![0, 1, 2]);
+    assert_eq!(solution.total_value, 8);
+}
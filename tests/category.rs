@@ -0,0 +1,67 @@
+//! Profits weighted by the category of the items
+
+use knapsack::{DpSolver, KnapsackInput, KnapsackInputError, KnapsackItem, KnapsackSolver};
+use std::collections::BTreeMap;
+
+/// Builds an input from (weight, profit) pairs
+fn input(items: &[(u64, u64)], capacity: u64) -> KnapsackInput {
+    let items = items
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    KnapsackInput::new(items, capacity, 1).unwrap()
+}
+
+fn profits(input: &KnapsackInput) -> Vec<u64> {
+    input.into_iter().map(|item| item.profit()).collect()
+}
+
+#[test]
+fn boosting_a_category_changes_the_optimum() {
+    let input = input(&[(5, 10), (5, 8)], 5);
+    assert_eq!(DpSolver.solve(&input).items, vec![0]);
+
+    // The profit 8 of the category 1 becomes 12, but the value keeps the original profits
+    let boosted = input
+        .with_category_weights(&[0, 1], &BTreeMap::from([(1, 1.5)]))
+        .unwrap();
+    let solution = boosted.solve(&DpSolver);
+
+    assert_eq!(solution.items, vec![1]);
+    assert_eq!(solution.total_value, 8);
+    assert_eq!(profits(boosted.weighted_input()), vec![10, 12]);
+}
+
+#[test]
+fn invalid_category_weights_are_rejected() {
+    let input = input(&[(5, 10), (5, 8)], 5);
+
+    assert!(matches!(
+        input.with_category_weights(&[0], &BTreeMap::new()),
+        Err(KnapsackInputError::CategoryCountMismatch)
+    ));
+    assert!(matches!(
+        input.with_category_weights(&[0, 1], &BTreeMap::from([(1, -1.0)])),
+        Err(KnapsackInputError::InvalidCategoryWeight { category: 1 })
+    ));
+}
+
+#[test]
+fn weighted_profits_above_2_pow_53_are_rejected() {
+    let input = input(&[(5, 1 << 52), (5, u64::MAX)], 5);
+
+    assert!(matches!(
+        input.with_category_weights(&[0, 1], &BTreeMap::from([(0, 4.0)])),
+        Err(KnapsackInputError::InexactWeightedProfit { index: 0 })
+    ));
+    assert!(matches!(
+        input.with_category_weights(&[0, 1], &BTreeMap::from([(1, 0.5)])),
+        Err(KnapsackInputError::InexactWeightedProfit { index: 1 })
+    ));
+
+    // The profits of the categories with a weight of 1 are kept exactly
+    let weighted = input
+        .with_category_weights(&[0, 1], &BTreeMap::from([(0, 2.0)]))
+        .unwrap();
+    assert_eq!(profits(weighted.weighted_input()), vec![1 << 53, u64::MAX]);
+}
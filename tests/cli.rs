@@ -0,0 +1,280 @@
+//! The command line interface: its output and its exit codes (see the exit code table in the README)
+
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+const INVALID_INPUT_EXIT_CODE: i32 = 3;
+const IO_ERROR_EXIT_CODE: i32 = 4;
+
+/// Writes the given content to a file of the temporary directory, named after the test so that
+/// the tests can run in parallel
+fn temp_file(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("knapsack-cli-{}-{}", std::process::id(), name));
+    fs::write(&path, content).unwrap();
+    path
+}
+
+/// Runs the dp method on the given input file and returns the exit code
+fn run(input_file: &PathBuf, output_file: &PathBuf) -> i32 {
+    Command::new(env!("CARGO_BIN_EXE_knapsack"))
+        .arg("--input-file")
+        .arg(input_file)
+        .arg("--output-file")
+        .arg(output_file)
+        .args(["run", "dp"])
+        .output()
+        .unwrap()
+        .status
+        .code()
+        .unwrap()
+}
+
+/// Runs the command line interface on the given input content with the given arguments (after the
+/// input and output files), returning the JSON output
+fn run_json(name: &str, content: &str, args: &[&str]) -> Value {
+    let input_file = temp_file(&format!("{}.kp", name), content);
+    let output_file =
+        std::env::temp_dir().join(format!("knapsack-cli-{}-{}.json", std::process::id(), name));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_knapsack"))
+        .arg("--input-file")
+        .arg(&input_file)
+        .arg("--output-file")
+        .arg(&output_file)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_str(&fs::read_to_string(&output_file).unwrap()).unwrap()
+}
+
+#[test]
+fn valid_input_succeeds() {
+    let input_file = temp_file("valid.kp", "2\n10\n\n5 4\n6 7\n");
+    let output_file =
+        std::env::temp_dir().join(format!("knapsack-cli-{}-valid.json", std::process::id()));
+
+    assert_eq!(run(&input_file, &output_file), 0);
+    assert!(fs::read_to_string(&output_file)
+        .unwrap()
+        .contains("\"total_value\":6"));
+}
+
+#[test]
+fn malformed_input_is_an_invalid_input() {
+    let input_file = temp_file("malformed.kp", "2\n10\n\n5 four\n6 7\n");
+    let output_file = std::env::temp_dir().join(format!(
+        "knapsack-cli-{}-malformed.json",
+        std::process::id()
+    ));
+
+    assert_eq!(run(&input_file, &output_file), INVALID_INPUT_EXIT_CODE);
+}
+
+#[test]
+fn missing_input_file_is_an_io_error() {
+    let input_file = std::env::temp_dir().join("knapsack-cli-missing-directory/input.kp");
+    let output_file =
+        std::env::temp_dir().join(format!("knapsack-cli-{}-missing.json", std::process::id()));
+
+    assert_eq!(run(&input_file, &output_file), IO_ERROR_EXIT_CODE);
+}
+
+#[test]
+fn unwritable_output_file_is_an_io_error() {
+    let input_file = temp_file("unwritable.kp", "2\n10\n\n5 4\n6 7\n");
+    let output_file = std::env::temp_dir().join("knapsack-cli-missing-directory/output.json");
+
+    assert_eq!(run(&input_file, &output_file), IO_ERROR_EXIT_CODE);
+}
+
+#[test]
+fn split_files_of_different_lengths_are_an_invalid_input() {
+    let weights_file = temp_file("mismatched.weights", "4\n7\n2\n");
+    let profits_file = temp_file("mismatched.profits", "5\n6\n");
+    let output_file = std::env::temp_dir().join(format!(
+        "knapsack-cli-{}-mismatched.json",
+        std::process::id()
+    ));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_knapsack"))
+        .arg("--weights-file")
+        .arg(&weights_file)
+        .arg("--profits-file")
+        .arg(&profits_file)
+        .args(["--capacity", "10", "--output-file"])
+        .arg(&output_file)
+        .args(["run", "dp"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(INVALID_INPUT_EXIT_CODE));
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("The number of weights (3) doesn't match the number of profits (2)"));
+}
+
+#[test]
+fn many_instances_give_one_result_each() {
+    let output = run_json(
+        "many",
+        "2\n10\n5 4\n6 7\n\n1\n3\n8 3\n\n3\n20\n1 1\n2 2\n3 3\n",
+        &["--many", "run", "dp"],
+    );
+
+    let values: Vec<u64> = output
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| result["total_value"].as_u64().unwrap())
+        .collect();
+    assert_eq!(values, vec![6, 8, 6]);
+}
+
+#[test]
+fn run_reports_the_solve_time() {
+    let output = run_json("timed", "2\n10\n\n5 4\n6 7\n", &["run", "dp"]);
+
+    assert!(output["solve_time_ns"].as_u64().unwrap() > 0);
+}
+
+#[test]
+fn column_order_flag_swaps_the_values_and_the_weights() {
+    let content = "2\n10\n\n5 4\n6 7\n";
+
+    let value_weight = run_json("value-weight", content, &["run", "dp"]);
+    let weight_value = run_json(
+        "weight-value",
+        content,
+        &["--column-order", "weight-value", "run", "dp"],
+    );
+    assert_eq!(value_weight["total_value"], 6);
+    assert_eq!(weight_value["total_value"], 7);
+}
+
+#[test]
+fn verbose_run_reports_the_utilization() {
+    let content = "2\n10\n\n5 4\n6 7\n";
+
+    assert_eq!(
+        run_json("quiet", content, &["run", "dp"]).get("utilization"),
+        None
+    );
+    assert_eq!(
+        run_json("verbose", content, &["--verbose", "run", "dp"])["utilization"],
+        0.7
+    );
+}
+
+#[test]
+fn or_library_format_flag() {
+    let output = run_json(
+        "or-library",
+        "4 10\n10 5\n40 4\n30 6\n50 3\n",
+        &["--format", "or-library", "run", "dp"],
+    );
+    assert_eq!(output["total_value"], 90);
+}
+
+#[test]
+fn output_includes_the_method_name() {
+    let content = "2\n10\n\n5 4\n6 7\n";
+
+    assert_eq!(run_json("dp-name", content, &["run", "dp"])["method"], "Dp");
+    assert_eq!(
+        run_json("minknap-name", content, &["run", "min-knap"])["method"],
+        "MinKnap"
+    );
+    let benchmark = run_json(
+        "benchmark-name",
+        content,
+        &[
+            "--bench-samples",
+            "10",
+            "--bench-time-secs",
+            "1",
+            "benchmark",
+            "dp",
+        ],
+    );
+    assert_eq!(benchmark["method"], "Dp");
+}
+
+#[test]
+fn verified_dp_run_succeeds() {
+    let output = run_json(
+        "verify.kp",
+        "2\n10\n\n5 4\n6 7\n",
+        &["--verify", "run", "dp"],
+    );
+    assert_eq!(output["total_value"], 6);
+    assert_eq!(output["items"], serde_json::json!([1]));
+}
+
+#[test]
+fn capacity_flag_overrides_the_file() {
+    // only the second item fits in the capacity of the file, but both fit in the override
+    let content = "2\n10\n\n5 4\n6 7\n";
+    let output = run_json("capacity.kp", content, &["--capacity", "11", "run", "dp"]);
+    assert_eq!(output["total_value"], 11);
+    assert_eq!(output["items"], serde_json::json!([0, 1]));
+
+    let input_file = temp_file("zero-capacity.kp", content);
+    let output = Command::new(env!("CARGO_BIN_EXE_knapsack"))
+        .arg("--input-file")
+        .arg(&input_file)
+        .args(["--capacity", "0", "run", "dp"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(INVALID_INPUT_EXIT_CODE));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid capacity"));
+}
+
+#[test]
+fn detailed_output_embeds_the_items() {
+    let output = run_json(
+        "detailed.kp",
+        "3\n10\n\n5 4\n6 7\n4 3\n",
+        &["--detailed", "run", "dp"],
+    );
+
+    let items = output["items"].as_array().unwrap();
+    let indices: Vec<u64> = items
+        .iter()
+        .map(|item| item["index"].as_u64().unwrap())
+        .collect();
+    assert_eq!(indices, vec![1, 2]);
+
+    let sum = |field: &str| -> u64 { items.iter().map(|item| item[field].as_u64().unwrap()).sum() };
+    assert_eq!(sum("profit"), output["total_value"].as_u64().unwrap());
+    assert_eq!(sum("weight"), output["total_weight"].as_u64().unwrap());
+    assert_eq!(output["total_value"], 10);
+    assert_eq!(output["total_weight"], 10);
+}
+
+#[test]
+fn missing_input_file_has_a_readable_message() {
+    let input_file = std::env::temp_dir().join("knapsack-cli-missing-directory/readable.kp");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_knapsack"))
+        .env_remove("RUST_BACKTRACE")
+        .env_remove("RUST_LIB_BACKTRACE")
+        .arg("--input-file")
+        .arg(&input_file)
+        .args(["run", "dp"])
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(IO_ERROR_EXIT_CODE));
+    assert!(stderr.starts_with(&format!(
+        "Error: Failed to open the input file \"{}\"",
+        input_file.display()
+    )));
+    assert!(!stderr.contains("panicked"), "{}", stderr);
+    assert!(!stderr.contains("backtrace"), "{}", stderr);
+}
@@ -0,0 +1,123 @@
+//! Seeded property test running every exact solver against brute force on small random instances
+
+use knapsack::{
+    BalKnapSolver, BktSolver, DpSolver, EqualWeightSolver, IddfsBktSolver, KnapsackInput,
+    KnapsackItem, KnapsackSolver, KnapsackView, MinKnapSolver, SolverCapabilities,
+};
+
+const INSTANCES: usize = 2000;
+const MAX_ITEMS: usize = 10;
+
+/// SplitMix64, so that the instances are the same on every run
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value in [0, bound]
+    fn up_to(&mut self, bound: u64) -> u64 {
+        self.next() % (bound + 1)
+    }
+}
+
+/// A random instance, with some zero weight and zero profit items, equal weights, and
+/// capacities ranging from tiny to u64::MAX
+fn random_input(rng: &mut Rng) -> (Vec<KnapsackItem>, u64) {
+    let n = rng.up_to(MAX_ITEMS as u64 - 1) as usize + 1;
+    let equal_weight = (rng.up_to(4) == 0).then(|| rng.up_to(10));
+    let items: Vec<KnapsackItem> = (0..n)
+        .map(|_| {
+            let weight = equal_weight.unwrap_or_else(|| match rng.up_to(5) {
+                0 => 0,
+                _ => rng.up_to(20),
+            });
+            let profit = match rng.up_to(5) {
+                0 => 0,
+                _ => rng.up_to(20),
+            };
+            KnapsackItem::new(weight, profit)
+        })
+        .collect();
+
+    let total_weight: u64 = items.iter().map(|item| item.weight()).sum();
+    let capacity = match rng.up_to(9) {
+        0 => u64::MAX,
+        1 => total_weight.max(1),
+        _ => rng.up_to(total_weight) + 1,
+    };
+
+    (items, capacity)
+}
+
+fn brute_force(items: &[KnapsackItem], capacity: u64) -> u64 {
+    (0u32..1 << items.len())
+        .filter_map(|mask| {
+            let selected = items
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| mask >> i & 1 == 1)
+                .map(|(_, item)| item);
+            let weight: u64 = selected.clone().map(|item| item.weight()).sum();
+            (weight <= capacity).then(|| selected.map(|item| item.profit()).sum())
+        })
+        .max()
+        .unwrap()
+}
+
+#[test]
+fn exact_solvers_match_brute_force() {
+    let solvers: [&dyn KnapsackSolver; 6] = [
+        &DpSolver,
+        &BktSolver,
+        &MinKnapSolver,
+        &EqualWeightSolver,
+        &IddfsBktSolver,
+        &BalKnapSolver,
+    ];
+    let mut rng = Rng(0x5eed);
+
+    for instance in 0..INSTANCES {
+        let (items, capacity) = random_input(&mut rng);
+        let optimum = brute_force(&items, capacity);
+        let weights: Vec<u64> = items.iter().map(|item| item.weight()).collect();
+        let profits: Vec<u64> = items.iter().map(|item| item.profit()).collect();
+        let view = KnapsackView::new(&weights, &profits, capacity).unwrap();
+        let input = KnapsackInput::new(items, capacity, 1).unwrap();
+
+        for solver in solvers {
+            if !solver.capabilities().contains(SolverCapabilities::EXACT)
+                || !solver.capabilities().can_solve(&input)
+            {
+                continue;
+            }
+            let context = format!("{} on instance {}: {:?}", solver.name(), instance, input);
+
+            let solution = solver.solve(&input);
+            assert_eq!(solution.verify(&input), Ok(()), "{}", context);
+            assert_eq!(solution.total_value, optimum, "{}", context);
+
+            assert_eq!(solver.solve_view(&view), solution, "{}", context);
+
+            let mut items = Vec::new();
+            assert_eq!(
+                solver.solve_into(&input, &mut items),
+                optimum,
+                "{}",
+                context
+            );
+
+            assert!(solver.has_solution_at_least(&input, optimum), "{}", context);
+            assert!(
+                !solver.has_solution_at_least(&input, optimum + 1),
+                "{}",
+                context
+            );
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Pins the exact items returned by each solver, so that changes to the tie-breaking between
+//! optimal solutions are noticed
+
+use knapsack::{
+    BalKnapSolver, BktSolver, DpSolver, EqualWeightSolver, FptasDpSolver, IddfsBktSolver,
+    KnapsackInput, KnapsackItem, KnapsackSolver, MinKnapSolver, RandomizedRoundingSolver,
+};
+
+/// Builds an input from (weight, profit) pairs
+fn input(items: &[(u64, u64)], capacity: u64) -> KnapsackInput {
+    let items = items
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    KnapsackInput::new(items, capacity, 1).unwrap()
+}
+
+/// Asserts that each solver returns the given items for the input
+fn assert_pinned(input: &KnapsackInput, expected: &[(&dyn KnapsackSolver, &[usize])]) {
+    for &(solver, items) in expected {
+        assert_eq!(solver.solve(input).items, items, "{}", solver.name());
+    }
+}
+
+#[test]
+fn pinned_items_with_several_optima() {
+    // the optimal value 10 is reached by {0, 1}, {2, 4} and {1, 4, 5}
+    let input = input(&[(2, 4), (3, 6), (4, 7), (5, 9), (1, 3), (1, 1), (6, 8)], 5);
+    let rounding = RandomizedRoundingSolver::default();
+
+    assert_pinned(
+        &input,
+        &[
+            (&DpSolver, &[0, 1]),
+            (&BktSolver, &[0, 1]),
+            (&MinKnapSolver, &[0, 1]),
+            (&EqualWeightSolver, &[0, 1]),
+            (&IddfsBktSolver, &[0, 1]),
+            (&BalKnapSolver, &[0, 1]),
+            (&FptasDpSolver, &[0, 1]),
+            (&rounding, &[2, 4]),
+        ],
+    );
+}
+
+#[test]
+fn pinned_items_with_equal_items() {
+    // any two of the items are optimal
+    let input = input(&[(3, 5), (3, 5), (3, 5), (3, 5), (3, 5)], 7);
+    let rounding = RandomizedRoundingSolver::default();
+
+    assert_pinned(
+        &input,
+        &[
+            (&DpSolver, &[0, 1]),
+            (&BktSolver, &[0, 1]),
+            (&MinKnapSolver, &[0, 1]),
+            (&EqualWeightSolver, &[0, 1]),
+            (&IddfsBktSolver, &[0, 1]),
+            (&BalKnapSolver, &[0, 1]),
+            (&FptasDpSolver, &[0, 1]),
+            (&rounding, &[0, 4]),
+        ],
+    );
+}
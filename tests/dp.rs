@@ -0,0 +1,413 @@
+//! Variants of the DP solver
+
+use knapsack::{
+    DpSolver, KnapsackInput, KnapsackItem, KnapsackSolution, KnapsackSolveError, KnapsackSolver,
+    SolveObjective, WideKnapsackInput,
+};
+use std::cell::{Cell, RefCell};
+
+/// SplitMix64, so that the instances are the same on every run
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value in [0, bound]
+    fn up_to(&mut self, bound: u64) -> u64 {
+        self.next() % (bound + 1)
+    }
+}
+
+/// A random instance of up to 20 items, with the capacity and the (weight, profit) pairs
+fn random_items(rng: &mut Rng) -> (Vec<(u64, u64)>, u64) {
+    let n = rng.up_to(19) as usize + 1;
+    let items: Vec<(u64, u64)> = (0..n).map(|_| (rng.up_to(30), rng.up_to(30))).collect();
+    let total_weight: u64 = items.iter().map(|&(weight, _)| weight).sum();
+    (items, rng.up_to(total_weight) + 1)
+}
+
+/// Builds an input from (weight, profit) pairs
+fn input(items: &[(u64, u64)], capacity: u64) -> KnapsackInput {
+    let items = items
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    KnapsackInput::new(items, capacity, 1).unwrap()
+}
+
+#[test]
+fn min_items_objective_picks_the_smaller_optimum() {
+    // {0, 1} and {2} both have the value 6 and the weight 4
+    let input = input(&[(2, 3), (2, 3), (4, 6), (3, 1)], 4);
+
+    let min_weight = DpSolver.solve_with_objective(&input, SolveObjective::MinWeight);
+    assert_eq!(min_weight, DpSolver.solve(&input));
+    assert_eq!(min_weight.items, vec![0, 1]);
+
+    let min_items = DpSolver.solve_with_objective(&input, SolveObjective::MinItems);
+    assert_eq!(min_items.items, vec![2]);
+    assert_eq!(min_items.total_value, 6);
+}
+
+#[test]
+fn min_items_objective_leaves_out_zero_profit_items() {
+    let input = input(&[(0, 0), (1, 5), (0, 2)], 1);
+
+    let solution = DpSolver.solve_with_objective(&input, SolveObjective::MinItems);
+    assert_eq!(solution.items, vec![1, 2]);
+    assert_eq!(solution.total_value, 7);
+}
+
+#[test]
+fn pareto_frontier_of_a_small_instance() {
+    // Only the set of all the items improves on the other points with the item 3, and the
+    // capacity is ignored
+    let input = input(&[(2, 3), (3, 4), (4, 5), (3, 1)], 1);
+
+    assert_eq!(
+        DpSolver.pareto_frontier(&input),
+        vec![
+            (0, 0),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 7),
+            (6, 8),
+            (7, 9),
+            (9, 12),
+            (12, 13)
+        ]
+    );
+}
+
+#[test]
+fn early_exit_matches_the_full_table() {
+    let mut rng = Rng(0x814);
+
+    for _ in 0..500 {
+        let (items, capacity) = random_items(&mut rng);
+        let input = input(&items, capacity);
+
+        let solution = DpSolver.solve(&input);
+        let full_table = DpSolver
+            .build_table(&input)
+            .best_value_for_capacity(capacity);
+        assert_eq!(solution.verify(&input), Ok(()), "{:?}", input);
+        assert_eq!(solution.total_value, full_table.total_value, "{:?}", input);
+    }
+}
+
+#[test]
+fn filling_stops_when_the_lp_bound_is_reached() {
+    // The first two items fill the knapsack, reaching the LP bound 20
+    let input = input(&[(5, 10), (5, 10), (1, 1), (1, 1)], 10);
+    let rows = Cell::new(0);
+
+    let solution = DpSolver.solve_with_progress(&input, |done, _| rows.set(done));
+    assert_eq!(solution.items, vec![0, 1]);
+    assert_eq!(rows.get(), 2);
+}
+
+#[test]
+fn ties_prefer_the_lightest_then_the_lowest_indices() {
+    // {0, 1}, {0, 4}, {1, 3} and {3, 4} all reach 7 with a weight of 5
+    let same_weight = input(&[(2, 3), (3, 4), (1, 1), (2, 3), (3, 4)], 5);
+    assert_eq!(DpSolver.solve(&same_weight).items, vec![0, 1]);
+
+    // {0} and {1} both reach 4, but {1} is lighter
+    let different_weights = input(&[(3, 4), (2, 4)], 3);
+    assert_eq!(DpSolver.solve(&different_weights).items, vec![1]);
+}
+
+#[test]
+fn bounded_solve_rejects_a_large_table() {
+    // 3 rows and a total profit of 3 000 000 000
+    let large_profits = input(
+        &[(1, 1_000_000_000), (2, 1_000_000_000), (3, 1_000_000_000)],
+        4,
+    );
+    let error = DpSolver.solve_bounded(&large_profits, 1 << 20).unwrap_err();
+    assert!(matches!(
+        error,
+        KnapsackSolveError::TableTooLarge {
+            cells: 9_000_000_003,
+            limit: 1_048_576
+        }
+    ));
+    assert!(error.to_string().contains("MinKnap"));
+
+    let small_profits = input(&[(1, 10), (2, 10), (3, 10)], 4);
+    assert_eq!(DpSolver::table_cells(&small_profits), 93);
+    let solution = DpSolver.solve_bounded(&small_profits, 93).unwrap();
+    assert_eq!(solution.total_value, 20);
+    assert!(DpSolver.solve_bounded(&small_profits, 92).is_err());
+}
+
+/// The optimal value for every capacity from 0 to the total weight, from a table of the minimum
+/// weight of each profit level filled over the whole profit range for every item
+fn reference_values(items: &[(u64, u64)]) -> Vec<u64> {
+    let total_profit: u64 = items.iter().map(|&(_, profit)| profit).sum();
+    let total_weight: u64 = items.iter().map(|&(weight, _)| weight).sum();
+    let mut min_weight = vec![u64::MAX; total_profit as usize + 1];
+    min_weight[0] = 0;
+    for &(weight, profit) in items {
+        for level in (profit as usize..=total_profit as usize).rev() {
+            let without = min_weight[level - profit as usize];
+            if without != u64::MAX {
+                min_weight[level] = min_weight[level].min(without + weight);
+            }
+        }
+    }
+
+    (0..=total_weight)
+        .map(|capacity| {
+            (0..=total_profit)
+                .filter(|&profit| min_weight[profit as usize] <= capacity)
+                .max()
+                .unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn capped_profit_levels_give_the_full_range_results() {
+    let mut rng = Rng(0x833);
+
+    for _ in 0..100 {
+        let (items, capacity) = random_items(&mut rng);
+        let input = input(&items, capacity);
+        let table = DpSolver.build_table(&input);
+
+        for (capacity, &value) in reference_values(&items).iter().enumerate() {
+            let solution = table.best_value_for_capacity(capacity as u64);
+            let weight: u64 = solution.items.iter().map(|&i| items[i].0).sum();
+            let profit: u64 = solution.items.iter().map(|&i| items[i].1).sum();
+            assert_eq!(solution.total_value, value, "{:?} {}", items, capacity);
+            assert_eq!(profit, value, "{:?} {}", items, capacity);
+            assert!(weight <= capacity as u64, "{:?} {}", items, capacity);
+        }
+    }
+}
+
+#[test]
+fn subset_sum_instances_match_the_table() {
+    let mut rng = Rng(0x834);
+
+    for _ in 0..300 {
+        let (items, capacity) = random_items(&mut rng);
+        let items: Vec<(u64, u64)> = items.iter().map(|&(weight, _)| (weight, weight)).collect();
+        let input = input(&items, capacity);
+
+        let solution = DpSolver.solve(&input);
+        let table_solution = DpSolver
+            .build_table(&input)
+            .best_value_for_capacity(capacity);
+        assert_eq!(solution.verify(&input), Ok(()), "{:?}", input);
+        assert_eq!(solution, table_solution, "{:?}", input);
+    }
+}
+
+#[test]
+fn capacity_queries_are_monotone() {
+    let input = input(
+        &[(2, 4), (3, 6), (4, 7), (5, 9), (1, 3), (1, 1), (6, 8)],
+        22,
+    );
+    let table = DpSolver.build_table(&input);
+
+    let values: Vec<u64> = (0..=22)
+        .map(|capacity| table.best_value_for_capacity(capacity).total_value)
+        .collect();
+    assert!(
+        values.windows(2).all(|pair| pair[0] <= pair[1]),
+        "{:?}",
+        values
+    );
+    assert_eq!(values[0], 0);
+    assert_eq!(values[5], 10);
+    assert_eq!(values[22], 38);
+    assert_eq!(table.best_value_for_capacity(22), DpSolver.solve(&input));
+}
+
+#[test]
+fn all_optima_of_an_instance_with_two_optima() {
+    // {0, 1} and {2} both reach 9, with different weights
+    let two_optima = input(&[(3, 4), (4, 5), (7, 9), (2, 1)], 7);
+
+    let mut optima = DpSolver.solve_all_optima(&two_optima, 10);
+    optima.sort();
+    assert_eq!(optima, vec![vec![0, 1], vec![2]]);
+    assert_eq!(DpSolver.solve_all_optima(&two_optima, 1).len(), 1);
+}
+
+#[test]
+fn progress_is_reported_once_per_item() {
+    // the LP bound (8) is only reached with the last item, so every row is filled
+    let input = input(&[(3, 4), (4, 5), (5, 6), (2, 3)], 6);
+    let calls = RefCell::new(Vec::new());
+
+    let solution =
+        DpSolver.solve_with_progress(&input, |done, total| calls.borrow_mut().push((done, total)));
+    assert_eq!(solution.items, vec![1, 3]);
+    assert_eq!(calls.into_inner(), vec![(1, 4), (2, 4), (3, 4), (4, 4)]);
+}
+
+#[test]
+fn linear_space_path_matches_the_full_table() {
+    let mut rng = Rng(0x848);
+
+    for _ in 0..30 {
+        let items: Vec<(u64, u64)> = (0..40)
+            .map(|_| (rng.up_to(99) + 1, rng.up_to(100)))
+            .collect();
+        let capacity = rng.up_to(1000) + 1;
+        let input = input(&items, capacity);
+        let weight =
+            |items_taken: &[usize]| -> u64 { items_taken.iter().map(|&i| items[i].0).sum() };
+
+        let solution = DpSolver.solve_linear_space(&input);
+        let full_table = DpSolver
+            .build_table(&input)
+            .best_value_for_capacity(capacity);
+        assert_eq!(solution.verify(&input), Ok(()), "{:?}", input);
+        assert_eq!(solution.total_value, full_table.total_value, "{:?}", input);
+        // both paths have the minimum weight, but may differ between several such item sets
+        assert_eq!(
+            weight(&solution.items),
+            weight(&full_table.items),
+            "{:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn min_weight_covers_of_a_small_instance() {
+    let input = input(&[(3, 4), (4, 5), (5, 6), (2, 3)], 1);
+    let cover = |target| DpSolver.min_weight_for_profit(&input, target);
+
+    // {0, 3} weighs 5, while the other sets reaching 7 weigh at least 6
+    assert_eq!(cover(7).unwrap().items, vec![0, 3]);
+    // {0, 2} weighs 8 and reaches 10, while {0, 1} (weighing 7) only reaches 9
+    assert_eq!(cover(10).unwrap().items, vec![0, 2]);
+    // {0, 1, 3} and {1, 2} weigh 9, but {0, 1, 3} has the highest profit
+    assert_eq!(cover(11).unwrap().total_value, 12);
+    assert_eq!(cover(18).unwrap().items, vec![0, 1, 2, 3]);
+    assert_eq!(cover(0).unwrap(), KnapsackSolution::default());
+    assert_eq!(cover(19), None);
+}
+
+#[test]
+fn max_items_forces_a_lower_value() {
+    let five_small = input(
+        &[(2, 3), (2, 3), (2, 3), (2, 3), (2, 3), (5, 7), (5, 7)],
+        10,
+    );
+
+    let unconstrained = DpSolver.solve_with_max_items(&five_small, None);
+    assert_eq!(unconstrained.items, vec![0, 1, 2, 3, 4]);
+    assert_eq!(unconstrained.total_value, 15);
+
+    let capped = DpSolver.solve_with_max_items(&five_small, Some(3));
+    assert_eq!(capped.items, vec![5, 6]);
+    assert_eq!(capped.total_value, 14);
+}
+
+#[test]
+fn wide_weights_above_half_of_u64_max() {
+    let half = 1u128 << 63;
+    // any two items weigh more than u64::MAX, and only items 1 and 2 fit together
+    let wide = WideKnapsackInput::new(
+        vec![half + 3, half + 1, half + 2],
+        vec![5, 6, 7],
+        2 * half + 3,
+    )
+    .unwrap();
+    let solution = DpSolver.solve_wide(&wide);
+    assert_eq!(solution.items, vec![1, 2]);
+    assert_eq!(solution.total_value, 13);
+
+    // scaling the weights and the capacity by 2^62 doesn't change the optimum
+    let mut rng = Rng(0x869);
+    for _ in 0..100 {
+        let (items, capacity) = random_items(&mut rng);
+        let wide = WideKnapsackInput::new(
+            items
+                .iter()
+                .map(|&(weight, _)| (weight as u128) << 62)
+                .collect(),
+            items.iter().map(|&(_, profit)| profit).collect(),
+            (capacity as u128) << 62,
+        )
+        .unwrap();
+
+        let narrow = input(&items, capacity);
+        assert_eq!(
+            DpSolver.solve_wide(&wide).total_value,
+            DpSolver.solve(&narrow).total_value,
+            "{:?}",
+            narrow
+        );
+    }
+}
+
+#[test]
+fn profit_cap_that_changes_the_solution() {
+    let capped_input = input(&[(5, 20), (3, 9), (2, 8)], 5);
+
+    let uncapped = DpSolver.solve_with_profit_cap(&capped_input, None);
+    assert_eq!(uncapped.items, vec![0]);
+    assert_eq!(uncapped.total_value, 20);
+
+    // item 0 only counts for 10, less than the 9 + 8 of the other items
+    let capped = DpSolver.solve_with_profit_cap(&capped_input, Some(10));
+    assert_eq!(capped.items, vec![1, 2]);
+    assert_eq!(capped.total_value, 17);
+    // the guarantee: at least the optimum minus the excess of its item over the cap
+    assert!(capped.total_value >= 20 - (20 - 10));
+
+    let loose_cap = DpSolver.solve_with_profit_cap(&capped_input, Some(20));
+    assert_eq!(loose_cap, uncapped);
+}
+
+#[test]
+fn value_curve_matches_individual_solves() {
+    let mut rng = Rng(0x896);
+    for _ in 0..20 {
+        let (items, capacity) = random_items(&mut rng);
+        let mut sweep = input(&items, capacity);
+        let capacities: Vec<u64> = (1..=capacity + 5).collect();
+
+        let curve = DpSolver.value_curve(&sweep, &capacities);
+        assert!(
+            curve.windows(2).all(|pair| pair[0] <= pair[1]),
+            "{:?}",
+            curve
+        );
+        for (&capacity, &value) in capacities.iter().zip(&curve) {
+            sweep.set_capacity(capacity).unwrap();
+            assert_eq!(value, DpSolver.solve(&sweep).total_value, "{:?}", sweep);
+        }
+    }
+}
+
+#[test]
+fn soft_capacity_with_a_low_and_a_high_penalty() {
+    // both items weigh one unit more than the capacity
+    let soft = input(&[(6, 20), (4, 5)], 9);
+
+    let (solution, penalized) = DpSolver.solve_with_soft_capacity(&soft, 2, 3);
+    assert_eq!(solution.items, vec![0, 1]);
+    assert_eq!(solution.total_value, 25);
+    assert_eq!(penalized, 23);
+
+    let (solution, penalized) = DpSolver.solve_with_soft_capacity(&soft, 10, 3);
+    assert_eq!(solution, DpSolver.solve(&soft));
+    assert_eq!(penalized, 20);
+}
@@ -0,0 +1,41 @@
+//! Groups of items of which at most one can be selected
+
+use knapsack::{DpSolver, KnapsackInput, KnapsackInputError, KnapsackItem, KnapsackSolver};
+
+/// Builds an input from (weight, profit) pairs
+fn input(items: &[(u64, u64)], capacity: u64) -> KnapsackInput {
+    let items = items
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    KnapsackInput::new(items, capacity, 1).unwrap()
+}
+
+#[test]
+fn exclusion_group_lowers_the_optimum() {
+    let input = input(&[(3, 6), (3, 5), (4, 4)], 10);
+    assert_eq!(DpSolver.solve(&input).total_value, 15);
+
+    // Only one of the two most efficient items can be selected
+    let exclusive = input.with_exclusion_groups(&[vec![0, 1]]).unwrap();
+    let solution = exclusive.solve();
+
+    assert_eq!(solution.items, vec![0, 2]);
+    assert_eq!(solution.total_value, 10);
+    assert_eq!(solution.verify(&input), Ok(()));
+    assert_eq!(exclusive.layers(), &[vec![0, 1], vec![2]]);
+}
+
+#[test]
+fn invalid_exclusion_groups_are_rejected() {
+    let input = input(&[(3, 6), (3, 5), (4, 4)], 10);
+
+    assert!(matches!(
+        input.with_exclusion_groups(&[vec![0, 3]]),
+        Err(KnapsackInputError::InvalidExclusionItem { index: 3 })
+    ));
+    assert!(matches!(
+        input.with_exclusion_groups(&[vec![0, 1], vec![1, 2]]),
+        Err(KnapsackInputError::OverlappingExclusionGroups { index: 1 })
+    ));
+}
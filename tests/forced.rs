@@ -0,0 +1,45 @@
+//! Items forced in or out of the solution
+
+use knapsack::{DpSolver, KnapsackInput, KnapsackInputError, KnapsackItem, KnapsackSolver};
+
+/// Builds an input from (weight, profit) pairs
+fn input(items: &[(u64, u64)], capacity: u64) -> KnapsackInput {
+    let items = items
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    KnapsackInput::new(items, capacity, 1).unwrap()
+}
+
+#[test]
+fn forced_items_are_reinjected() {
+    let input = input(&[(4, 10), (3, 6), (3, 6), (5, 1)], 10);
+    assert_eq!(DpSolver.solve(&input).items, vec![0, 1, 2]);
+
+    // The solver only sees the items 0 and 2, with the capacity 5 left by the item 3
+    let forced = input.with_forced(&[3, 3], &[1]).unwrap();
+    assert_eq!(forced.remaining_capacity(), 5);
+
+    let solution = forced.solve(&DpSolver);
+    assert_eq!(solution.items, vec![0, 3]);
+    assert_eq!(solution.total_value, 11);
+    assert_eq!(solution.verify(&input), Ok(()));
+}
+
+#[test]
+fn invalid_forced_items_are_rejected() {
+    let input = input(&[(4, 10), (3, 6), (8, 6)], 10);
+
+    assert!(matches!(
+        input.with_forced(&[0], &[3]),
+        Err(KnapsackInputError::InvalidForcedItem { index: 3 })
+    ));
+    assert!(matches!(
+        input.with_forced(&[0, 1], &[2, 1]),
+        Err(KnapsackInputError::ConflictingForcedItem { index: 1 })
+    ));
+    assert!(matches!(
+        input.with_forced(&[0, 2], &[]),
+        Err(KnapsackInputError::InfeasibleForcedItems)
+    ));
+}
@@ -0,0 +1,192 @@
+//! Construction and transformations of the inputs
+
+use knapsack::{
+    DpSolver, KnapsackInput, KnapsackInputError, KnapsackItem, KnapsackSolver, MinKnapSolver,
+};
+
+/// Builds an input from (weight, profit) pairs
+fn input(items: &[(u64, u64)], capacity: u64) -> KnapsackInput {
+    let items = items
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    KnapsackInput::new(items, capacity, 1).unwrap()
+}
+
+fn weights(input: &KnapsackInput) -> Vec<u64> {
+    input.into_iter().map(|item| item.weight()).collect()
+}
+
+fn profits(input: &KnapsackInput) -> Vec<u64> {
+    input.into_iter().map(|item| item.profit()).collect()
+}
+
+#[test]
+fn float_profits_are_rounded_at_the_decimal_place() {
+    let input =
+        KnapsackInput::from_float_items(&[(3, 0.125), (4, 2.344), (5, 1.2), (6, 0.004)], 9, 2)
+            .unwrap();
+
+    // 12.5 is rounded up and 0.4 down
+    assert_eq!(profits(&input), vec![13, 234, 120, 0]);
+
+    let solution = DpSolver.solve(&input);
+    assert_eq!(solution.items, vec![1, 2]);
+    assert_eq!(input.to_float_profit(solution.total_value), 3.54);
+}
+
+#[test]
+fn invalid_float_profits_are_rejected() {
+    assert!(matches!(
+        KnapsackInput::from_float_items(&[(3, 1.0), (4, -2.0)], 9, 2),
+        Err(KnapsackInputError::InvalidFloatProfit { index: 1 })
+    ));
+    assert!(matches!(
+        KnapsackInput::from_float_items(&[(3, f64::NAN)], 9, 2),
+        Err(KnapsackInputError::InvalidFloatProfit { index: 0 })
+    ));
+    assert!(matches!(
+        KnapsackInput::from_float_items(&[(3, 1.0)], 9, 20),
+        Err(KnapsackInputError::InvalidDecimals)
+    ));
+}
+
+#[test]
+fn scaled_weights_keep_the_optimum() {
+    let kilograms = input(&[(2, 4), (3, 6), (4, 7), (5, 9), (1, 3), (1, 1), (6, 8)], 9);
+    let grams = kilograms.scale_weights(1000).unwrap();
+
+    assert_eq!(
+        weights(&grams),
+        vec![2000, 3000, 4000, 5000, 1000, 1000, 6000]
+    );
+    assert_eq!(profits(&grams), profits(&kilograms));
+    for solver in [&DpSolver as &dyn KnapsackSolver, &MinKnapSolver] {
+        assert_eq!(
+            solver.solve(&grams),
+            solver.solve(&kilograms),
+            "{}",
+            solver.name()
+        );
+    }
+
+    assert!(matches!(
+        kilograms.scale_weights(u64::MAX / 8),
+        Err(KnapsackInputError::WeightOverflow)
+    ));
+    assert!(matches!(
+        kilograms.scale_weights(0),
+        Err(KnapsackInputError::ZeroCapacity)
+    ));
+}
+
+#[test]
+fn dominated_item_is_dropped_without_changing_the_optimum() {
+    // item 2 is dominated by item 0 (lighter and more profitable), and both don't fit together
+    let original = input(&[(3, 7), (2, 2), (4, 6), (1, 3)], 6);
+    let (reduced, mapping) = original.remove_dominated();
+
+    assert_eq!(mapping, vec![0, 1, 3]);
+    assert_eq!(weights(&reduced), vec![3, 2, 1]);
+
+    let solution = DpSolver.solve(&reduced);
+    let remapped: Vec<usize> = solution.items.iter().map(|&i| mapping[i]).collect();
+    assert_eq!(solution.total_value, DpSolver.solve(&original).total_value);
+    assert_eq!(remapped, vec![0, 1, 3]);
+}
+
+#[test]
+fn identical_items_are_grouped() {
+    let mut items = vec![(3, 5); 1000];
+    items.push((1, 1));
+    let copies = input(&items, 1000);
+
+    let grouped = copies.group_identical();
+    // 1, 2, 4, ..., 256 and 489 copies, and the last item
+    assert_eq!(grouped.grouped_input().into_iter().count(), 11);
+
+    let solution = grouped.solve(&DpSolver);
+    assert_eq!(solution.verify(&copies), Ok(()));
+    assert_eq!(solution.items.len(), 334);
+    assert_eq!(solution.items.last(), Some(&1000));
+    assert_eq!(solution.total_value, 333 * 5 + 1);
+}
+
+#[test]
+fn total_and_max_weights() {
+    let small = input(&[(3, 5), (0, 1), (7, 2)], 10);
+    assert_eq!(small.total_weight(), 10);
+    assert_eq!(small.max_weight(), Some(7));
+    #[allow(deprecated)]
+    let max_cost = small.max_cost();
+    assert_eq!(max_cost, 7);
+
+    let huge = input(&[(u64::MAX - 1, 5), (3, 1)], 10);
+    assert_eq!(huge.total_weight(), u64::MAX);
+    assert_eq!(huge.max_weight(), Some(u64::MAX - 1));
+
+    let empty = input(&[], 10);
+    assert_eq!(empty.total_weight(), 0);
+    assert_eq!(empty.max_weight(), None);
+}
+
+#[test]
+fn empty_input_extended_then_solved() {
+    let mut extended = input(&[], 10);
+    assert_eq!(DpSolver.solve(&extended).total_value, 0);
+
+    extended.push_item(4, 5);
+    extended.extend_items([KnapsackItem::new(7, 6), KnapsackItem::new(3, 4)]);
+    extended.push_item(5, 3);
+
+    assert_eq!(weights(&extended), vec![4, 7, 3, 5]);
+    assert_eq!(profits(&extended), vec![5, 6, 4, 3]);
+    let solution = MinKnapSolver.solve(&extended);
+    assert_eq!(solution.items, vec![1, 2]);
+    assert_eq!(solution.total_value, 10);
+}
+
+#[test]
+fn known_number_of_dominated_items() {
+    // item 1 is dominated by item 0, item 3 by items 0 and 2, and the second copy of item 4 by
+    // the first one, while the zero weight item 6 is the least profitable, so it dominates none
+    let dominated = input(
+        &[(2, 5), (3, 5), (1, 4), (4, 3), (5, 9), (5, 9), (0, 1)],
+        10,
+    );
+    assert_eq!(dominated.count_dominated(), 3);
+
+    let efficient_frontier = input(&[(1, 1), (2, 3), (3, 5), (4, 8)], 10);
+    assert_eq!(efficient_frontier.count_dominated(), 0);
+}
+
+#[test]
+fn capacity_is_validated_when_set() {
+    let mut small = input(&[(4, 5), (7, 6)], 10);
+
+    assert!(matches!(
+        small.set_capacity(0),
+        Err(KnapsackInputError::ZeroCapacity)
+    ));
+    // the rejected capacity leaves the input unchanged, so only one item still fits
+    assert_eq!(DpSolver.solve(&small).items, vec![1]);
+
+    assert!(small.set_capacity(11).is_ok());
+    assert_eq!(DpSolver.solve(&small).items, vec![0, 1]);
+}
+
+#[test]
+fn iterated_profits_sum_to_the_total_profit() {
+    let items = input(&[(4, 5), (7, 6), (0, 3)], 10);
+
+    let mut borrowed_sum = 0;
+    for item in &items {
+        borrowed_sum += item.profit();
+    }
+    assert_eq!(Some(borrowed_sum), items.max_item_profit());
+
+    let expected = items.max_item_profit();
+    let owned_sum: u64 = items.into_iter().map(|item| item.profit()).sum();
+    assert_eq!(Some(owned_sum), expected);
+    assert_eq!(owned_sum, 14);
+}
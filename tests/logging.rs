@@ -0,0 +1,58 @@
+//! Logging of the solver decisions (run with `cargo test --features log`)
+
+use knapsack::{DpSolver, KnapsackInput, KnapsackItem, KnapsackSolver, MinKnapSolver};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::sync::Mutex;
+
+/// Logger keeping the level and the text of every record
+struct TestLogger {
+    records: Mutex<Vec<(Level, String)>>,
+}
+
+impl Log for TestLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.records
+            .lock()
+            .unwrap()
+            .push((record.level(), record.args().to_string()));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: TestLogger = TestLogger {
+    records: Mutex::new(Vec::new()),
+};
+
+#[test]
+fn solves_log_their_decisions() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Trace);
+
+    let items = [(3, 4), (4, 5), (5, 6), (2, 3)]
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    let input = KnapsackInput::new(items, 10, 1).unwrap();
+    MinKnapSolver.solve(&input);
+    DpSolver.solve(&input);
+
+    let records = LOGGER.records.lock().unwrap();
+    let logged = |level: Level, prefix: &str| {
+        records
+            .iter()
+            .any(|(record_level, text)| *record_level == level && text.starts_with(prefix))
+    };
+    assert!(logged(Level::Debug, "break solution"), "{:?}", records);
+    assert!(logged(Level::Trace, "core expanded"), "{:?}", records);
+    assert!(
+        logged(Level::Debug, "min-knap explored a core"),
+        "{:?}",
+        records
+    );
+    assert!(logged(Level::Debug, "dp table of"), "{:?}", records);
+}
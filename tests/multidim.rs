@@ -0,0 +1,138 @@
+//! Multidimensional knapsack
+
+use knapsack::{
+    DpSolver, KnapsackInput, KnapsackInputError, KnapsackItem, KnapsackSolver, LpBound,
+    MultiDimBnbSolver, MultiDimItem, MultiDimKnapsackInput, UpperBound,
+};
+
+/// SplitMix64, so that the instances are the same on every run
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value in [0, bound]
+    fn up_to(&mut self, bound: u64) -> u64 {
+        self.next() % (bound + 1)
+    }
+}
+
+/// A random instance of up to 10 items, as (weights, profit) pairs and capacities
+fn random_instance(rng: &mut Rng) -> (Vec<(Vec<u64>, u64)>, Vec<u64>) {
+    let dimensions = rng.up_to(2) as usize + 1;
+    let n = rng.up_to(9) as usize + 1;
+    let items = (0..n)
+        .map(|_| {
+            let weights = (0..dimensions).map(|_| rng.up_to(20)).collect();
+            (weights, rng.up_to(20))
+        })
+        .collect();
+    let capacities = (0..dimensions).map(|_| rng.up_to(50) + 1).collect();
+    (items, capacities)
+}
+
+/// Bound taking all the remaining items, which prunes less than `LpBound`
+struct RemainingProfits;
+
+impl UpperBound for RemainingProfits {
+    fn bound(&self, input: &MultiDimKnapsackInput, next_item: usize, _: &[u64]) -> u64 {
+        input.items()[next_item..]
+            .iter()
+            .map(|item| item.profit())
+            .sum()
+    }
+}
+
+fn brute_force(items: &[(Vec<u64>, u64)], capacities: &[u64]) -> u64 {
+    (0u32..1 << items.len())
+        .filter_map(|mask| {
+            let selected: Vec<&(Vec<u64>, u64)> = items
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| mask >> i & 1 == 1)
+                .map(|(_, item)| item)
+                .collect();
+            let fits = (0..capacities.len()).all(|d| {
+                selected.iter().map(|(weights, _)| weights[d]).sum::<u64>() <= capacities[d]
+            });
+            fits.then(|| selected.iter().map(|(_, profit)| profit).sum())
+        })
+        .max()
+        .unwrap()
+}
+
+#[test]
+fn bnb_matches_brute_force_with_each_bound() {
+    let mut rng = Rng(0x3d);
+
+    for _ in 0..500 {
+        let (items, capacities) = random_instance(&mut rng);
+        let optimum = brute_force(&items, &capacities);
+        let input = MultiDimKnapsackInput::new(
+            items
+                .iter()
+                .map(|(weights, profit)| MultiDimItem::new(weights.clone(), *profit))
+                .collect(),
+            capacities.clone(),
+        )
+        .unwrap();
+
+        for bound in [&LpBound as &dyn UpperBound, &RemainingProfits] {
+            let solution = MultiDimBnbSolver::new(bound).solve(&input);
+            assert_eq!(solution.total_value, optimum, "{:?}", input);
+
+            // The selected items fit along every dimension and add up to the value
+            for (d, &capacity) in capacities.iter().enumerate() {
+                let weight: u64 = solution.items.iter().map(|&i| items[i].0[d]).sum();
+                assert!(weight <= capacity, "{:?}", input);
+            }
+            let value: u64 = solution.items.iter().map(|&i| items[i].1).sum();
+            assert_eq!(value, optimum, "{:?}", input);
+        }
+    }
+}
+
+#[test]
+fn single_dimension_matches_dp() {
+    let items = [(3, 4), (4, 5), (5, 6), (2, 3)]
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    let input = KnapsackInput::new(items, 9, 1).unwrap();
+    let multidim_input = MultiDimKnapsackInput::try_from(&input).unwrap();
+
+    assert_eq!(
+        MultiDimBnbSolver::default()
+            .solve(&multidim_input)
+            .total_value,
+        DpSolver.solve(&input).total_value
+    );
+}
+
+#[test]
+fn invalid_inputs_are_rejected() {
+    let item = |weights: &[u64], profit| MultiDimItem::new(weights.to_vec(), profit);
+
+    assert!(matches!(
+        MultiDimKnapsackInput::new(vec![item(&[1, 2], 1)], vec![]),
+        Err(KnapsackInputError::MissingCapacity)
+    ));
+    assert!(matches!(
+        MultiDimKnapsackInput::new(vec![item(&[1, 2], 1)], vec![3, 0]),
+        Err(KnapsackInputError::ZeroCapacity)
+    ));
+    assert!(matches!(
+        MultiDimKnapsackInput::new(vec![item(&[1], 1)], vec![3, 4]),
+        Err(KnapsackInputError::DimensionMismatch)
+    ));
+    assert!(matches!(
+        MultiDimKnapsackInput::new(vec![item(&[1, 2], u64::MAX), item(&[1, 2], 1)], vec![3, 4]),
+        Err(KnapsackInputError::ProfitOverflow)
+    ));
+}
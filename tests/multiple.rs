@@ -0,0 +1,51 @@
+//! Multiple knapsack problem
+
+use knapsack::{DpSolver, KnapsackInput, KnapsackItem, KnapsackSolver, MultipleKnapsackSolver};
+
+fn items(items: &[(u64, u64)]) -> Vec<KnapsackItem> {
+    items
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect()
+}
+
+/// Checks that every item is assigned at most once and that every knapsack holds its items
+fn assert_feasible(items: &[KnapsackItem], capacities: &[u64], assignments: &[Vec<usize>]) {
+    let mut assigned: Vec<usize> = assignments.iter().flatten().copied().collect();
+    assigned.sort_unstable();
+    assigned.dedup();
+    assert_eq!(
+        assigned.len(),
+        assignments.iter().map(Vec::len).sum::<usize>()
+    );
+
+    for (knapsack, &capacity) in assignments.iter().zip(capacities) {
+        let weight: u64 = knapsack.iter().map(|&i| items[i].weight()).sum();
+        assert!(weight <= capacity);
+    }
+}
+
+#[test]
+fn two_knapsacks_beat_a_single_one() {
+    let items = items(&[(5, 10), (5, 10), (5, 10), (4, 3)]);
+    let single = DpSolver.solve(&KnapsackInput::new(items.clone(), 10, 1).unwrap());
+    assert_eq!(single.total_value, 20);
+
+    let capacities = vec![5, 10];
+    let solution = MultipleKnapsackSolver::new(capacities.clone()).solve(&items);
+
+    assert_feasible(&items, &capacities, &solution.assignments);
+    assert_eq!(solution.assignments, vec![vec![2], vec![0, 1]]);
+    assert_eq!(solution.total_value, 30);
+}
+
+#[test]
+fn zero_weight_items_go_to_the_largest_knapsack() {
+    let items = items(&[(0, 1), (3, 4), (0, 2)]);
+    let capacities = vec![2, 3];
+    let solution = MultipleKnapsackSolver::new(capacities.clone()).solve(&items);
+
+    assert_feasible(&items, &capacities, &solution.assignments);
+    assert_eq!(solution.assignments, vec![vec![], vec![0, 1, 2]]);
+    assert_eq!(solution.total_value, 7);
+}
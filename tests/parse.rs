@@ -0,0 +1,217 @@
+//! Parsing of the input formats
+
+use knapsack::{
+    ColumnOrder, DpSolver, KnapsackInput, KnapsackInputError, KnapsackItem, KnapsackSolver,
+    ParseWarning,
+};
+
+/// The (weight, profit) pairs of the items of an input
+fn items(input: &KnapsackInput) -> Vec<(u64, u64)> {
+    input
+        .into_iter()
+        .map(|item| (item.weight(), item.profit()))
+        .collect()
+}
+
+#[test]
+fn item_errors_report_the_line_and_its_content() {
+    let error = KnapsackInput::parse_input("2\n10\n\n5 4\n6 x\n".as_bytes()).unwrap_err();
+    assert!(matches!(
+        error,
+        KnapsackInputError::InvalidItemWeight { line: 5, ref content } if content == "6 x"
+    ));
+    assert_eq!(error.to_string(), "Invalid item weight on line 5: \"6 x\"");
+
+    let error = KnapsackInput::parse_input("2\n10\n-5 4\n6 7\n".as_bytes()).unwrap_err();
+    assert!(matches!(
+        error,
+        KnapsackInputError::InvalidItemValue { line: 3, ref content } if content == "-5 4"
+    ));
+
+    let error = KnapsackInput::parse_input("2\n10\n5 4\n6 7 label extra\n".as_bytes()).unwrap_err();
+    assert!(matches!(
+        error,
+        KnapsackInputError::InvalidItemSpecification { line: 4, ref content }
+            if content == "6 7 label extra"
+    ));
+
+    let input = KnapsackInput::parse_input("2\n10\n5 4\n6 7\n".as_bytes()).unwrap();
+    assert_eq!(items(&input), vec![(4, 5), (7, 6)]);
+}
+
+#[test]
+fn inputs_parse_from_strings() {
+    let input: KnapsackInput = "2\n10\n5 4\n6 7\n".parse().unwrap();
+    assert_eq!(items(&input), vec![(4, 5), (7, 6)]);
+
+    let error = "two\n10\n5 4\n6 7\n".parse::<KnapsackInput>().unwrap_err();
+    assert!(matches!(
+        error,
+        KnapsackInputError::InvalidItemCount { line: 1, ref content } if content == "two"
+    ));
+}
+
+#[test]
+fn split_streams_of_different_lengths_are_rejected() {
+    let input = KnapsackInput::parse_split("4\n7\n".as_bytes(), "5\n\n6\n".as_bytes(), 10).unwrap();
+    assert_eq!(items(&input), vec![(4, 5), (7, 6)]);
+
+    let error =
+        KnapsackInput::parse_split("4\n7\n2\n".as_bytes(), "5\n6\n".as_bytes(), 10).unwrap_err();
+    assert!(matches!(
+        error,
+        KnapsackInputError::ItemCountMismatch {
+            weights: 3,
+            profits: 2
+        }
+    ));
+}
+
+#[test]
+fn lenient_inputs_produce_warnings() {
+    let (input, warnings) =
+        KnapsackInput::parse_input_with_warnings("3\n10\n5 4\n6 0\n3 12\n1 1\n2 2\n".as_bytes())
+            .unwrap();
+    assert_eq!(items(&input), vec![(4, 5), (0, 6), (12, 3)]);
+    assert_eq!(
+        warnings,
+        vec![
+            ParseWarning::ExtraItemLines {
+                declared: 3,
+                ignored: 2
+            },
+            ParseWarning::ZeroWeightItem { item: 1 },
+            ParseWarning::ItemTooHeavy { item: 2 },
+        ]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "declared 3 items but 5 item lines were found (2 ignored)"
+    );
+
+    let (_, warnings) =
+        KnapsackInput::parse_input_with_warnings("2\n10\n5 4\n6 7\n".as_bytes()).unwrap();
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn concatenated_instances_are_parsed_in_order() {
+    let content = "2\n10\n5 4\n6 7\n\n1\n3\n8 3\n\n\n3\n20\n1 1\n2 2\n3 3\n";
+    let instances = KnapsackInput::parse_many(content.as_bytes()).unwrap();
+
+    let instances: Vec<Vec<(u64, u64)>> = instances.iter().map(items).collect();
+    assert_eq!(
+        instances,
+        vec![
+            vec![(4, 5), (7, 6)],
+            vec![(3, 8)],
+            vec![(1, 1), (2, 2), (3, 3)]
+        ]
+    );
+
+    let error = KnapsackInput::parse_many("1\n3\n8 3\n2\n10\n5 4\n".as_bytes()).unwrap_err();
+    assert!(matches!(error, KnapsackInputError::InsufficientItems));
+}
+
+#[test]
+fn inflated_item_count_is_insufficient_items() {
+    let error =
+        KnapsackInput::parse_input("18446744073709551615\n10\n5 4\n6 7\n".as_bytes()).unwrap_err();
+    assert!(matches!(error, KnapsackInputError::InsufficientItems));
+
+    let mut binary = Vec::new();
+    for value in [u64::MAX, 10, 4, 5] {
+        binary.extend_from_slice(&value.to_le_bytes());
+    }
+    let error = KnapsackInput::parse_binary(binary.as_slice()).unwrap_err();
+    assert!(matches!(error, KnapsackInputError::InsufficientItems));
+}
+
+#[test]
+fn labels_round_trip_through_a_solve() {
+    let input =
+        KnapsackInput::parse_input("3\n10\n5 4 apple\n6 9\n3 2 sku-42\n".as_bytes()).unwrap();
+    let solution = DpSolver.solve(&input);
+
+    assert_eq!(solution.items, vec![0, 2]);
+    assert_eq!(
+        solution.labeled_items(&input),
+        vec![Some("apple"), Some("sku-42")]
+    );
+    assert_eq!(input.label(1), None);
+
+    let unlabeled = KnapsackInput::parse_input("2\n10\n5 4\n6 7\n".as_bytes()).unwrap();
+    let solution = DpSolver.solve(&unlabeled);
+    assert_eq!(solution.labeled_items(&unlabeled), vec![None]);
+}
+
+#[test]
+fn single_item_lines() {
+    let item = KnapsackItem::parse_line("5 4").unwrap();
+    assert_eq!((item.weight(), item.profit()), (4, 5));
+    let item = KnapsackItem::try_from("  12\t3 label ").unwrap();
+    assert_eq!((item.weight(), item.profit()), (3, 12));
+
+    for line in ["5", "5 4 label extra", ""] {
+        assert!(
+            matches!(
+                KnapsackItem::parse_line(line),
+                Err(KnapsackInputError::InvalidItemSpecification { line: 1, ref content })
+                    if content == line
+            ),
+            "{:?}",
+            line
+        );
+    }
+    assert!(matches!(
+        KnapsackItem::parse_line("five 4"),
+        Err(KnapsackInputError::InvalidItemValue { line: 1, .. })
+    ));
+    assert!(matches!(
+        KnapsackItem::try_from("5 4.5"),
+        Err(KnapsackInputError::InvalidItemWeight { line: 1, .. })
+    ));
+}
+
+#[test]
+fn both_column_orders() {
+    let content = "3\n10\n5 4\n6 7\n3 2\n";
+
+    let value_weight =
+        KnapsackInput::parse_input_with_order(content.as_bytes(), ColumnOrder::ValueWeight)
+            .unwrap();
+    let weight_value =
+        KnapsackInput::parse_input_with_order(content.as_bytes(), ColumnOrder::WeightValue)
+            .unwrap();
+    assert_eq!(items(&value_weight), vec![(4, 5), (7, 6), (2, 3)]);
+    assert_eq!(items(&weight_value), vec![(5, 4), (6, 7), (3, 2)]);
+    assert_eq!(
+        items(&KnapsackInput::parse_input(content.as_bytes()).unwrap()),
+        items(&value_weight)
+    );
+}
+
+#[test]
+fn or_library_block() {
+    let block = "4 10\n10 5\n40 4\n30 6\n50 3\n";
+
+    let input = KnapsackInput::parse_or_library(block.as_bytes()).unwrap();
+    assert_eq!(items(&input), vec![(5, 10), (4, 40), (6, 30), (3, 50)]);
+    assert_eq!(DpSolver.solve(&input).items, vec![1, 3]);
+}
+
+#[test]
+fn binary_round_trip() {
+    let original_items = [(4, 5), (0, 3), (7, u64::MAX), (u64::MAX, 1)]
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    let original = KnapsackInput::new(original_items, 11, 1).unwrap();
+
+    let mut binary = Vec::new();
+    original.write_binary(&mut binary).unwrap();
+    assert_eq!(binary.len(), 8 * (2 + 2 * 4));
+
+    let parsed = KnapsackInput::parse_binary(binary.as_slice()).unwrap();
+    assert_eq!(items(&parsed), items(&original));
+}
@@ -0,0 +1,235 @@
+use knapsack::{
+    BalKnapSolver, BktSolver, DpSolver, FptasDpSolver, IddfsBktSolver, KnapsackInput,
+    KnapsackInputError, KnapsackItem, KnapsackSolution, KnapsackSolveError, KnapsackSolver,
+    MinKnapSolver, SolveObjective,
+};
+
+/// Builds an input from (weight, profit) pairs
+fn input(items: &[(u64, u64)], capacity: u64) -> KnapsackInput {
+    let items = items
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    KnapsackInput::new(items, capacity, 1).unwrap()
+}
+
+#[test]
+fn bkt_threshold_counts_zero_weight_items() {
+    let input = input(&[(0, 1), (3, 1), (0, 0), (0, 1), (3, 3)], 4);
+
+    // {0, 3, 4} reaches 5
+    assert!(BktSolver.has_solution_at_least(&input, 5));
+    assert!(!BktSolver.has_solution_at_least(&input, 6));
+}
+
+#[test]
+fn dp_max_items_with_huge_capacity() {
+    let input = input(&[(5, 10), (6, 11), (7, 12)], u64::MAX);
+
+    let solution = DpSolver.solve_with_max_items(&input, Some(1));
+    assert_eq!(solution.verify(&input), Ok(()));
+    assert_eq!(solution.total_value, 12);
+}
+
+#[test]
+fn dp_with_huge_capacity() {
+    let input = input(&[(5, 10), (6, 11), (7, 12)], u64::MAX);
+
+    let solution = DpSolver.solve(&input);
+    assert_eq!(solution.verify(&input), Ok(()));
+    assert_eq!(solution.total_value, 33);
+}
+
+#[test]
+fn exclusion_groups_with_huge_capacity() {
+    let input = input(&[(5, 10), (6, 11), (7, 12)], u64::MAX);
+
+    let solution = input.with_exclusion_groups(&[vec![0, 1]]).unwrap().solve();
+    assert_eq!(solution.verify(&input), Ok(()));
+    assert_eq!(solution.total_value, 23);
+}
+
+#[test]
+fn dp_min_items_with_huge_capacity() {
+    let input = input(&[(5, 10), (6, 11), (7, 12)], u64::MAX);
+
+    let solution = DpSolver.solve_with_objective(&input, SolveObjective::MinItems);
+    assert_eq!(solution.verify(&input), Ok(()));
+    assert_eq!(solution.total_value, 33);
+}
+
+#[test]
+fn dp_min_weight_for_profit_without_items() {
+    let input = input(&[], 10);
+
+    assert_eq!(
+        DpSolver.min_weight_for_profit(&input, 0),
+        Some(KnapsackSolution::default())
+    );
+    assert_eq!(DpSolver.min_weight_for_profit(&input, 1), None);
+}
+
+#[test]
+fn dp_all_optima_without_items() {
+    let input = input(&[], 10);
+
+    assert_eq!(
+        DpSolver.solve_all_optima(&input, 10),
+        vec![Vec::<usize>::new()]
+    );
+}
+
+#[test]
+fn dp_table_without_items() {
+    let input = input(&[], 10);
+
+    let table = DpSolver.build_table(&input);
+    assert_eq!(
+        table.best_value_for_capacity(5),
+        KnapsackSolution::default()
+    );
+}
+
+#[test]
+fn dp_pareto_frontier_without_items() {
+    let input = input(&[], 10);
+
+    assert_eq!(DpSolver.pareto_frontier(&input), vec![(0, 0)]);
+}
+
+#[test]
+fn dp_reports_overflowing_profits() {
+    let input = input(&[(1, u64::MAX), (2, u64::MAX)], 3);
+
+    assert!(matches!(
+        DpSolver.try_solve(&input),
+        Err(KnapsackSolveError::TotalOverflow { quantity: "profit" })
+    ));
+}
+
+#[test]
+fn search_solvers_report_overflowing_totals() {
+    let heavy = input(
+        &[(u64::MAX / 2, 1), (u64::MAX / 2, 2), (u64::MAX / 2, 3)],
+        u64::MAX,
+    );
+    let valuable = input(
+        &[(1, u64::MAX / 2), (2, u64::MAX / 2), (3, u64::MAX / 2)],
+        3,
+    );
+    let solvers: [&dyn KnapsackSolver; 4] =
+        [&MinKnapSolver, &BktSolver, &BalKnapSolver, &IddfsBktSolver];
+
+    for solver in solvers {
+        assert!(matches!(
+            solver.try_solve(&heavy),
+            Err(KnapsackSolveError::TotalOverflow { quantity: "weight" })
+        ));
+        assert!(matches!(
+            solver.try_solve(&valuable),
+            Err(KnapsackSolveError::TotalOverflow { quantity: "profit" })
+        ));
+    }
+}
+
+#[test]
+fn grouping_huge_profits() {
+    let input = input(
+        &[(1, u64::MAX / 2), (1, u64::MAX / 2), (1, u64::MAX / 2)],
+        1,
+    );
+
+    let grouped = input.group_identical();
+    assert_eq!(grouped.grouped_input().into_iter().count(), 2);
+}
+
+#[test]
+fn forced_items_with_overflowing_weight() {
+    let input = input(
+        &[(u64::MAX / 2, 1), (u64::MAX / 2, 2), (u64::MAX / 2, 3)],
+        u64::MAX,
+    );
+
+    assert!(matches!(
+        input.with_forced(&[0, 1, 2], &[]),
+        Err(KnapsackInputError::InfeasibleForcedItems)
+    ));
+}
+
+#[test]
+fn dominated_items() {
+    // item 1 is dominated by items 0 and 2, and item 2 by item 0
+    let input = input(&[(2, 5), (3, 4), (2, 5), (1, 1), (4, 6)], 5);
+
+    assert_eq!(input.count_dominated(), 2);
+    // item 1 doesn't fit together with its dominators, while item 2 does
+    assert_eq!(input.remove_dominated().1, vec![0, 2, 3, 4]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn capacity_parse_error_reports_the_line() {
+    let error = KnapsackInput::parse_input("2\n\n1O\n3 4\n5 6\n".as_bytes()).unwrap_err();
+
+    assert!(matches!(
+        error,
+        KnapsackInputError::InvalidCapacity { line: 3, ref content } if content == "1O"
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn or_library_header_with_extra_tokens() {
+    let error = KnapsackInput::parse_or_library("2 10 7\n3 4\n5 6\n".as_bytes()).unwrap_err();
+
+    assert!(matches!(
+        error,
+        KnapsackInputError::InvalidHeader { line: 1, ref content } if content == "2 10 7"
+    ));
+}
+
+#[test]
+fn fptas_scale_with_wrapping_u32_product() {
+    // granularity * n = 2^32, which wraps to 0 on u32, scaling every profit to the same value
+    let n = 1 << 16;
+    let mut items = vec![KnapsackItem::new(1, 1); n - 1];
+    items.push(KnapsackItem::new(1, 1000));
+    let input = KnapsackInput::new(items, 1, 1 << 16).unwrap();
+
+    let solution = FptasDpSolver.solve(&input);
+    assert_eq!(solution.items, vec![n - 1]);
+    assert_eq!(solution.total_value, 1000);
+}
+
+#[test]
+fn minknap_break_item_is_the_first_item() {
+    // the only positive weight item exceeds the capacity
+    let heavy = input(&[(0, 2), (20, 50), (0, 3), (0, 1)], 10);
+    let solution = MinKnapSolver.solve(&heavy);
+    assert_eq!(solution.items, vec![0, 2, 3]);
+    assert_eq!(solution.total_value, 6);
+
+    // the most efficient item exceeds the capacity, but the other ones fit
+    let efficient = input(&[(3, 3), (11, 40), (4, 5), (0, 1)], 10);
+    let solution = MinKnapSolver.solve(&efficient);
+    assert_eq!(solution.items, vec![0, 2, 3]);
+    assert_eq!(solution.total_value, 9);
+}
+
+#[test]
+fn minknap_with_capacity_above_half_of_u64_max() {
+    let items = [(3, 4), (5, 6), (7, 9), (9, 10)];
+    let scale = 1 << 59;
+    let scaled: Vec<(u64, u64)> = items
+        .iter()
+        .map(|&(weight, profit)| (weight * scale, profit))
+        .collect();
+
+    // the capacity plus the weight of the break solution doesn't fit in a u64
+    let large = input(&scaled, 20 * scale);
+    let small = input(&items, 20);
+
+    let solution = MinKnapSolver.solve(&large);
+    assert_eq!(solution.verify(&large), Ok(()));
+    assert_eq!(solution, DpSolver.solve(&small));
+}
@@ -0,0 +1,44 @@
+//! The LP relaxation of the problem and its break item
+
+use knapsack::{KnapsackInput, KnapsackItem};
+
+/// Builds an input from (weight, profit) pairs
+fn input(items: &[(u64, u64)], capacity: u64) -> KnapsackInput {
+    let items = items
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    KnapsackInput::new(items, capacity, 1).unwrap()
+}
+
+#[test]
+fn fractional_break_item() {
+    // items 2 and 0 (the most efficient ones) weigh 6, leaving 4 of the 5 units of item 1, while
+    // item 3 is heavier than the capacity
+    let input = input(&[(4, 8), (5, 5), (2, 6), (20, 10)], 10);
+
+    let (index, fraction) = input.break_item().unwrap();
+    assert_eq!(index, 1);
+    assert!((fraction - 0.8).abs() < 1e-12, "{}", fraction);
+    assert_eq!(input.lp_bound(), 6 + 8 + 4);
+}
+
+#[test]
+fn no_break_item_when_everything_fits() {
+    let input = input(&[(4, 8), (5, 5), (20, 10)], 10);
+
+    assert_eq!(input.break_item(), None);
+    assert_eq!(input.lp_bound(), 13);
+}
+
+#[test]
+fn hand_computed_break_efficiency() {
+    // items 0 and 1 weigh 7, so item 2 breaks with a profit of 12 for a weight of 8
+    let breaking = input(&[(3, 9), (4, 8), (8, 12), (1, 1)], 10);
+
+    assert_eq!(breaking.break_item().map(|(index, _)| index), Some(2));
+    assert_eq!(breaking.break_efficiency(), Some(1.5));
+
+    let everything_fits = input(&[(3, 9), (4, 8)], 10);
+    assert_eq!(everything_fits.break_efficiency(), None);
+}
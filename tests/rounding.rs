@@ -0,0 +1,85 @@
+//! Randomized rounding of the LP relaxation
+
+use knapsack::{DpSolver, KnapsackInput, KnapsackItem, KnapsackSolver, RandomizedRoundingSolver};
+use std::collections::HashSet;
+
+/// SplitMix64, so that the instances are the same on every run
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value in [1, bound]
+    fn up_to(&mut self, bound: u64) -> u64 {
+        self.next() % bound + 1
+    }
+}
+
+/// A random instance of up to 30 items, whose capacity fits about half of them
+fn random_input(rng: &mut Rng) -> KnapsackInput {
+    let n = rng.up_to(30) as usize;
+    let items: Vec<KnapsackItem> = (0..n)
+        .map(|_| KnapsackItem::new(rng.up_to(100), rng.up_to(100)))
+        .collect();
+    let capacity = items.iter().map(|item| item.weight()).sum::<u64>() / 2 + 1;
+    KnapsackInput::new(items, capacity, 1).unwrap()
+}
+
+#[test]
+fn same_seed_gives_same_solution() {
+    let mut rng = Rng(1);
+
+    for _ in 0..100 {
+        let input = random_input(&mut rng);
+        let seed = rng.next();
+
+        assert_eq!(
+            RandomizedRoundingSolver::new(8, seed).solve(&input),
+            RandomizedRoundingSolver::new(8, seed).solve(&input),
+            "{:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn solutions_are_feasible_and_close_to_the_optimum() {
+    let solver = RandomizedRoundingSolver::default();
+    let mut rng = Rng(2);
+    let (mut total_value, mut total_optimum) = (0, 0);
+
+    for _ in 0..500 {
+        let input = random_input(&mut rng);
+        let solution = solver.solve(&input);
+        let optimum = DpSolver.solve(&input).total_value;
+
+        assert_eq!(solution.verify(&input), Ok(()), "{:?}", input);
+        assert!(solution.total_value <= optimum, "{:?}", input);
+        total_value += solution.total_value;
+        total_optimum += optimum;
+    }
+
+    assert!(total_value * 100 >= total_optimum * 98);
+}
+
+#[test]
+fn trials_round_several_fractional_items() {
+    // Rounding only the break item would give at most two solutions over all the seeds
+    let mut rng = Rng(3);
+    let items = (0..20)
+        .map(|_| KnapsackItem::new(rng.up_to(100), rng.up_to(100)))
+        .collect();
+    let input = KnapsackInput::new(items, 500, 1).unwrap();
+
+    let solutions: HashSet<Vec<usize>> = (0..64)
+        .map(|seed| RandomizedRoundingSolver::new(1, seed).solve(&input).items)
+        .collect();
+
+    assert!(solutions.len() > 2, "{:?}", solutions);
+}
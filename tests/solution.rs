@@ -0,0 +1,135 @@
+//! Comparison and inspection of the solutions
+
+use knapsack::{
+    DpSolver, KnapsackInput, KnapsackItem, KnapsackSolution, KnapsackSolver, MinKnapSolver,
+    SolutionError,
+};
+use std::collections::HashSet;
+
+/// Builds an input from (weight, profit) pairs
+fn input(items: &[(u64, u64)], capacity: u64) -> KnapsackInput {
+    let items = items
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    KnapsackInput::new(items, capacity, 1).unwrap()
+}
+
+#[test]
+fn solutions_compare_by_items_or_by_value() {
+    // the optimal value 10 is reached by {0, 1}, {2, 4} and {1, 4, 5}
+    let input = input(&[(2, 4), (3, 6), (4, 7), (5, 9), (1, 3), (1, 1), (6, 8)], 5);
+    let dp = DpSolver.solve(&input);
+    let other_optimum = KnapsackSolution {
+        items: vec![2, 4],
+        total_value: 10,
+    };
+
+    assert_eq!(dp, MinKnapSolver.solve(&input));
+    assert_ne!(dp, other_optimum);
+    assert!(dp.value_eq(&other_optimum));
+    assert!(!dp.value_eq(&KnapsackSolution::default()));
+
+    let distinct: HashSet<KnapsackSolution> =
+        HashSet::from([dp.clone(), other_optimum, dp.clone()]);
+    assert_eq!(distinct.len(), 2);
+}
+
+#[test]
+fn decision_vector_bits_match_the_items() {
+    let input = input(&[(2, 4), (3, 6), (4, 7), (5, 9), (1, 3), (1, 1), (6, 8)], 9);
+    let solution = MinKnapSolver.solve(&input);
+
+    let decision_vector = solution.decision_vector(7);
+    assert_eq!(decision_vector.len(), 7);
+    assert_eq!(
+        decision_vector.iter_ones().collect::<Vec<_>>(),
+        solution.items
+    );
+    assert_eq!(
+        KnapsackSolution::default().decision_vector(3).count_ones(),
+        0
+    );
+}
+
+#[test]
+fn optimum_filling_the_capacity_has_full_utilization() {
+    // {0, 1} fills the capacity exactly
+    let full = input(&[(4, 5), (6, 7), (5, 5)], 10);
+    let solution = DpSolver.solve(&full);
+
+    assert_eq!(solution.items, vec![0, 1]);
+    assert_eq!(solution.utilization(&full), 1.0);
+    assert_eq!(KnapsackSolution::default().utilization(&full), 0.0);
+}
+
+#[test]
+fn corrupted_solutions_fail_verification() {
+    let original = input(&[(4, 5), (6, 7), (5, 5)], 10);
+    let solution = DpSolver.solve(&original);
+    assert_eq!(solution.verify(&original), Ok(()));
+
+    let mut wrong_value = solution.clone();
+    wrong_value.total_value += 1;
+    assert_eq!(
+        wrong_value.verify(&original),
+        Err(SolutionError::ValueMismatch {
+            reported: 13,
+            actual: 12
+        })
+    );
+
+    let mut overweight = solution.clone();
+    overweight.items.push(2);
+    overweight.total_value += 5;
+    assert_eq!(
+        overweight.verify(&original),
+        Err(SolutionError::CapacityExceeded {
+            weight: 15,
+            capacity: 10
+        })
+    );
+
+    let mut invalid_item = solution;
+    invalid_item.items.push(3);
+    assert_eq!(
+        invalid_item.verify(&original),
+        Err(SolutionError::InvalidItem { index: 3 })
+    );
+}
+
+#[test]
+fn efficiency_of_a_known_solution() {
+    // {0, 1} has a value of 12 for a weight of 10
+    let known = input(&[(4, 5), (6, 7), (5, 5)], 10);
+    let solution = DpSolver.solve(&known);
+    assert_eq!(solution.efficiency(&known), Some(1.2));
+
+    // only zero weight items are selected
+    let free = input(&[(0, 3), (0, 4), (5, 1)], 4);
+    let solution = DpSolver.solve(&free);
+    assert_eq!(solution.items, vec![0, 1]);
+    assert_eq!(solution.efficiency(&free), None);
+}
+
+#[test]
+fn greedy_fill_of_an_under_filled_solution() {
+    let under_filled = input(&[(4, 5), (6, 7), (5, 5), (1, 3)], 10);
+    let mut solution = KnapsackSolution {
+        items: vec![1],
+        total_value: 7,
+    };
+
+    // the most efficient item fits in the remaining capacity of 4, and then none of the others
+    solution.greedy_fill(&under_filled);
+    assert_eq!(solution.items, vec![1, 3]);
+    assert_eq!(solution.total_value, 10);
+    assert_eq!(solution.verify(&under_filled), Ok(()));
+
+    let mut infeasible = KnapsackSolution {
+        items: vec![1, 2],
+        total_value: 12,
+    };
+    infeasible.greedy_fill(&under_filled);
+    assert_eq!(infeasible.items, vec![1, 2]);
+}
@@ -0,0 +1,24 @@
+//! Solving from async code (run with `cargo test --features tokio`)
+
+use knapsack::{solve_async, DpSolver, KnapsackInput, KnapsackItem, KnapsackSolver, MinKnapSolver};
+
+#[test]
+fn async_solve_matches_the_synchronous_one() {
+    let items = [(3, 4), (4, 5), (5, 6), (2, 3)]
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    let input = KnapsackInput::new(items, 9, 1).unwrap();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    let dp = runtime
+        .block_on(solve_async(DpSolver, input.clone()))
+        .unwrap();
+    let minknap = runtime
+        .block_on(solve_async(MinKnapSolver, input.clone()))
+        .unwrap();
+    assert_eq!(dp, DpSolver.solve(&input));
+    assert_eq!(minknap, MinKnapSolver.solve(&input));
+}
@@ -0,0 +1,589 @@
+//! Behaviour of the individual solvers
+
+use knapsack::{
+    compare_solvers, BalKnapSolver, BktSolver, CachedMinKnapSolver, DpSolver, EqualWeightSolver,
+    FptasDpSolver, IddfsBktSolver, KnapsackInput, KnapsackInputError, KnapsackItem, KnapsackMethod,
+    KnapsackSolution, KnapsackSolver, KnapsackView, MinKnapSolver, RandomizedRoundingSolver,
+    SolverCapabilities,
+};
+use std::collections::HashMap;
+
+/// SplitMix64, so that the instances are the same on every run
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value in [low, high]
+    fn between(&mut self, low: u64, high: u64) -> u64 {
+        low + self.next() % (high - low + 1)
+    }
+}
+
+/// Builds an input from (weight, profit) pairs
+fn input(items: &[(u64, u64)], capacity: u64) -> KnapsackInput {
+    let items = items
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    KnapsackInput::new(items, capacity, 1).unwrap()
+}
+
+/// The optimal value, over every subset of the items
+fn brute_force(input: &KnapsackInput, capacity: u64) -> u64 {
+    let items: Vec<&KnapsackItem> = input.into_iter().collect();
+    (0u32..1 << items.len())
+        .filter_map(|mask| {
+            let selected = (0..items.len()).filter(|&i| mask >> i & 1 == 1);
+            let weight: u64 = selected.clone().map(|i| items[i].weight()).sum();
+            (weight <= capacity).then(|| selected.map(|i| items[i].profit()).sum())
+        })
+        .max()
+        .unwrap()
+}
+
+#[test]
+fn equal_weight_solver_matches_dp() {
+    let input = input(&[(4, 7), (4, 2), (4, 9), (4, 5), (4, 9)], 13);
+
+    let solution = EqualWeightSolver.solve(&input);
+    assert_eq!(solution.total_value, DpSolver.solve(&input).total_value);
+    assert_eq!(solution.items, vec![0, 2, 4]);
+}
+
+#[test]
+fn equal_weight_solver_takes_every_zero_weight_item() {
+    let input = input(&[(0, 7), (0, 2), (0, 9)], 1);
+
+    let solution = EqualWeightSolver.solve(&input);
+    assert_eq!(solution.items, vec![0, 1, 2]);
+    assert_eq!(solution.total_value, 18);
+}
+
+#[test]
+fn minknap_with_weights_and_profits_near_u32_max() {
+    // The profit table of DP can't be allocated for such profits, so the optimum is computed by
+    // brute force
+    let mut rng = Rng(0x810);
+    let low = u32::MAX as u64 - (1 << 20);
+
+    for _ in 0..200 {
+        let n = rng.between(1, 12) as usize;
+        let items: Vec<(u64, u64)> = (0..n)
+            .map(|_| {
+                (
+                    rng.between(low, u32::MAX as u64),
+                    rng.between(low, u32::MAX as u64),
+                )
+            })
+            .collect();
+        let capacity = rng.between(low, u32::MAX as u64 * n as u64);
+        let input = input(&items, capacity);
+
+        let solution = MinKnapSolver.solve(&input);
+        assert_eq!(solution.verify(&input), Ok(()), "{:?}", input);
+        assert_eq!(
+            solution.total_value,
+            brute_force(&input, capacity),
+            "{:?}",
+            input
+        );
+    }
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn backtracking_expansions_of_a_trivial_instance() {
+    // Only one of the two items fits: item 0 is pushed as included, item 1 as excluded, then both
+    // are popped and item 1 is pushed and popped as included on the branch without item 0
+    let input = input(&[(1, 1), (1, 2)], 1);
+
+    let (solution, metrics) = BktSolver.solve_with_metrics(&input);
+    assert_eq!(solution.items, vec![1]);
+    assert_eq!(metrics.stack_pushes, 3);
+    assert_eq!(metrics.stack_pops, 3);
+}
+
+#[test]
+fn solvers_report_their_capabilities() {
+    let exact = SolverCapabilities::EXACT | SolverCapabilities::ZERO_WEIGHT_ITEMS;
+    let approximate = SolverCapabilities::APPROXIMATE | SolverCapabilities::ZERO_WEIGHT_ITEMS;
+    let scalable = SolverCapabilities::SCALABLE;
+    let rounding = RandomizedRoundingSolver::default();
+
+    let expected: [(&dyn KnapsackSolver, SolverCapabilities); 8] = [
+        (&DpSolver, exact | scalable),
+        (&MinKnapSolver, exact | scalable),
+        (&BalKnapSolver, exact | scalable),
+        (&EqualWeightSolver, exact | scalable),
+        (&BktSolver, exact),
+        (&IddfsBktSolver, exact),
+        (&FptasDpSolver, approximate | scalable),
+        (&rounding, approximate | scalable),
+    ];
+    for (solver, capabilities) in expected {
+        assert_eq!(solver.capabilities(), capabilities, "{}", solver.name());
+    }
+
+    let zero_weight = input(&[(0, 1), (2, 3)], 2);
+    assert!(exact.can_solve(&zero_weight));
+    assert!(!SolverCapabilities::EXACT.can_solve(&zero_weight));
+}
+
+#[test]
+fn every_solver_returns_sorted_items() {
+    let rounding = RandomizedRoundingSolver::default();
+    let solvers: [&dyn KnapsackSolver; 8] = [
+        &DpSolver,
+        &BktSolver,
+        &FptasDpSolver,
+        &MinKnapSolver,
+        &EqualWeightSolver,
+        &IddfsBktSolver,
+        &BalKnapSolver,
+        &rounding,
+    ];
+    let methods: Vec<KnapsackMethod> = solvers.iter().map(|solver| solver.method()).collect();
+    assert_eq!(methods, KnapsackMethod::all());
+
+    let mut rng = Rng(0x820);
+    for _ in 0..50 {
+        let items: Vec<(u64, u64)> = (0..12)
+            .map(|_| (rng.between(1, 20), rng.between(0, 20)))
+            .collect();
+        let mixed_weights = input(&items, rng.between(10, 100));
+        let equal_weights = input(
+            &items
+                .iter()
+                .map(|&(_, profit)| (3, profit))
+                .collect::<Vec<_>>(),
+            20,
+        );
+
+        for solver in solvers {
+            let instance = if solver.method() == KnapsackMethod::EqualWeight {
+                &equal_weights
+            } else {
+                &mixed_weights
+            };
+            let items = solver.solve(instance).items;
+            assert!(
+                items.windows(2).all(|pair| pair[0] < pair[1]),
+                "{}: {:?}",
+                solver.name(),
+                items
+            );
+        }
+    }
+}
+
+#[test]
+fn minknap_with_custom_orderings_is_optimal() {
+    let profit_first = |a: &KnapsackItem, b: &KnapsackItem| b.profit().cmp(&a.profit());
+    let weight_first = |a: &KnapsackItem, b: &KnapsackItem| a.weight().cmp(&b.weight());
+    let by_index = |_: &KnapsackItem, _: &KnapsackItem| std::cmp::Ordering::Equal;
+    let mut rng = Rng(0x827);
+
+    for _ in 0..100 {
+        let items: Vec<(u64, u64)> = (0..15)
+            .map(|_| (rng.between(0, 30), rng.between(0, 30)))
+            .collect();
+        let input = input(&items, rng.between(1, 150));
+        let optimum = DpSolver.solve(&input).total_value;
+
+        for solution in [
+            MinKnapSolver.solve_with_ordering(&input, profit_first),
+            MinKnapSolver.solve_with_ordering(&input, weight_first),
+            MinKnapSolver.solve_with_ordering(&input, by_index),
+        ] {
+            assert_eq!(solution.verify(&input), Ok(()), "{:?}", input);
+            assert_eq!(solution.total_value, optimum, "{:?}", input);
+        }
+    }
+}
+
+#[test]
+fn iddfs_matches_dp() {
+    let mut rng = Rng(0x829);
+
+    for _ in 0..200 {
+        let n = rng.between(0, 14) as usize;
+        let items: Vec<(u64, u64)> = (0..n)
+            .map(|_| (rng.between(0, 40), rng.between(0, 40)))
+            .collect();
+        let input = input(&items, rng.between(1, 200));
+
+        let solution = IddfsBktSolver.solve(&input);
+        assert_eq!(solution.verify(&input), Ok(()), "{:?}", input);
+        assert_eq!(
+            solution.total_value,
+            DpSolver.solve(&input).total_value,
+            "{:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn optimality_gap_brackets_the_true_gap() {
+    let rounding = RandomizedRoundingSolver::default();
+    let approximate: [&dyn KnapsackSolver; 2] = [&FptasDpSolver, &rounding];
+    let mut rng = Rng(0x831);
+
+    for _ in 0..200 {
+        let items = (0..12)
+            .map(|_| KnapsackItem::new(rng.between(1, 30), rng.between(1, 100)))
+            .collect();
+        let input = KnapsackInput::new(items, rng.between(10, 150), 4).unwrap();
+        let optimum = DpSolver.solve(&input).total_value;
+
+        for solver in approximate {
+            let gap = solver.solve(&input).optimality_gap(&input);
+            assert!(
+                gap.value <= optimum && optimum <= gap.upper_bound,
+                "{:?}",
+                input
+            );
+
+            let true_gap = (optimum - gap.value) as f64 / optimum as f64;
+            assert!(true_gap <= gap.relative_gap(), "{:?}", input);
+        }
+
+        let fptas_value = FptasDpSolver.solve(&input).total_value as f64;
+        assert!(fptas_value >= FptasDpSolver.guarantee(&input) * optimum as f64);
+    }
+}
+
+#[test]
+fn solve_from_borrowed_slices() {
+    let mut rng = Rng(0x835);
+
+    for _ in 0..100 {
+        let weights: Vec<u64> = (0..12).map(|_| rng.between(0, 30)).collect();
+        let profits: Vec<u64> = (0..12).map(|_| rng.between(0, 30)).collect();
+        let capacity = rng.between(1, 150);
+        let view = KnapsackView::new(&weights, &profits, capacity).unwrap();
+        let input = KnapsackInput::from_slices(&weights, &profits, capacity).unwrap();
+
+        // DP and MinKnap read the slices directly, the backtracking solver copies the items
+        for solver in [&DpSolver as &dyn KnapsackSolver, &MinKnapSolver, &BktSolver] {
+            assert_eq!(
+                solver.solve_view(&view),
+                solver.solve(&input),
+                "{}: {:?}",
+                solver.name(),
+                input
+            );
+        }
+    }
+
+    assert!(matches!(
+        KnapsackView::new(&[1, 2], &[3], 5),
+        Err(KnapsackInputError::ItemCountMismatch {
+            weights: 2,
+            profits: 1
+        })
+    ));
+}
+
+#[test]
+fn balknap_matches_dp_on_strongly_correlated_instances() {
+    let mut rng = Rng(0x837);
+
+    for _ in 0..200 {
+        let items: Vec<(u64, u64)> = (0..rng.between(1, 25))
+            .map(|_| {
+                let weight = rng.between(1, 100);
+                (weight, weight + 1)
+            })
+            .collect();
+        let total_weight: u64 = items.iter().map(|&(weight, _)| weight).sum();
+        let input = input(&items, rng.between(1, total_weight));
+
+        let solution = BalKnapSolver.solve(&input);
+        assert_eq!(solution.verify(&input), Ok(()), "{:?}", input);
+        assert_eq!(
+            solution.total_value,
+            DpSolver.solve(&input).total_value,
+            "{:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn minknap_counts_zero_weight_items_once() {
+    let zero_weight = input(&[(0, 5), (4, 6), (0, 7), (5, 4), (0, 3), (3, 5)], 7);
+
+    let solution = MinKnapSolver.solve(&zero_weight);
+    assert_eq!(solution.items, vec![0, 1, 2, 4, 5]);
+    assert_eq!(solution.total_value, 5 + 6 + 7 + 3 + 5);
+    assert_eq!(solution.verify(&zero_weight), Ok(()));
+}
+
+#[test]
+fn fptas_value_is_the_same_with_both_tables() {
+    let mut rng = Rng(0x858);
+    let mut weight_indexed_instances = 0;
+
+    for round in 0..100 {
+        let items: Vec<(u64, u64)> = (0..10)
+            .map(|_| (rng.between(1, 40), rng.between(1, 1000)))
+            .collect();
+        // the small capacities make the weight indexed table the smaller one
+        let capacity = if round % 2 == 0 {
+            rng.between(1, 20)
+        } else {
+            rng.between(200, 400)
+        };
+        let granularity = 4;
+        let original = KnapsackInput::new(
+            items
+                .iter()
+                .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+                .collect(),
+            capacity,
+            granularity,
+        )
+        .unwrap();
+
+        // the scaling of FPTAS: profit * granularity * n / max_profit, at least 1
+        let max_profit = items.iter().map(|&(_, profit)| profit).max().unwrap();
+        let scale = f64::from(granularity) * items.len() as f64 / max_profit as f64;
+        let scaled_items: Vec<(u64, u64)> = items
+            .iter()
+            .map(|&(weight, profit)| (weight, ((profit as f64 * scale) as u64).max(1)))
+            .collect();
+        let scaled = input(&scaled_items, capacity);
+        if DpSolver::weight_table_cells(&scaled) < DpSolver::table_cells(&scaled) {
+            weight_indexed_instances += 1;
+        }
+
+        let scaled_value = |solution_items: &[usize]| -> u64 {
+            solution_items.iter().map(|&i| scaled_items[i].1).sum()
+        };
+        let profit_indexed = DpSolver.solve(&scaled);
+        let weight_indexed = DpSolver.solve_weight_indexed(&scaled);
+        let fptas = FptasDpSolver.solve(&original);
+        assert_eq!(
+            profit_indexed.total_value, weight_indexed.total_value,
+            "{:?}",
+            original
+        );
+        assert_eq!(
+            scaled_value(&fptas.items),
+            profit_indexed.total_value,
+            "{:?}",
+            original
+        );
+        assert_eq!(fptas.verify(&original), Ok(()), "{:?}", original);
+    }
+
+    assert!(weight_indexed_instances > 0 && weight_indexed_instances < 100);
+}
+
+#[test]
+fn one_buffer_reused_across_three_solves() {
+    let instances = [
+        input(&[(3, 4), (4, 5), (5, 6), (2, 3)], 9),
+        input(&[(8, 10), (1, 1)], 5),
+        input(&[(1, 2), (1, 2), (1, 2)], 3),
+    ];
+
+    for solver in [&DpSolver as &dyn KnapsackSolver, &MinKnapSolver, &BktSolver] {
+        let mut items = Vec::with_capacity(3);
+        let buffer = items.as_ptr();
+        for instance in &instances {
+            let total_value = solver.solve_into(instance, &mut items);
+            let solution = solver.solve(instance);
+            assert_eq!(items, solution.items, "{}", solver.name());
+            assert_eq!(total_value, solution.total_value, "{}", solver.name());
+            // no solution has more than 3 items, so the buffer is never reallocated
+            assert_eq!(items.as_ptr(), buffer, "{}", solver.name());
+        }
+    }
+}
+
+#[test]
+fn reachable_and_unreachable_thresholds() {
+    let mut rng = Rng(0x873);
+    for _ in 0..100 {
+        let items: Vec<(u64, u64)> = (0..10)
+            .map(|_| (rng.between(0, 30), rng.between(0, 30)))
+            .collect();
+        let input = input(&items, rng.between(1, 100));
+        let optimum = DpSolver.solve(&input).total_value;
+
+        for solver in [&DpSolver as &dyn KnapsackSolver, &BktSolver, &MinKnapSolver] {
+            assert!(
+                solver.has_solution_at_least(&input, optimum),
+                "{}",
+                solver.name()
+            );
+            assert!(
+                !solver.has_solution_at_least(&input, optimum + 1),
+                "{}",
+                solver.name()
+            );
+        }
+    }
+
+    // The search stops at the first solution reaching the threshold, and the threshold above the
+    // LP bound is rejected at the root, while a full search of the 2^60 subsets would never end
+    let items: Vec<(u64, u64)> = (0..60).map(|i| (i % 7 + 1, i % 11 + 1)).collect();
+    let large = input(&items, 100);
+    let lp_bound = large.lp_bound();
+    assert!(BktSolver.has_solution_at_least(&large, 50));
+    assert!(!BktSolver.has_solution_at_least(&large, lp_bound + 1));
+    // The DP table of the large profits is never allocated
+    let huge_profits = input(&[(5, u64::MAX / 4), (6, u64::MAX / 4)], 10);
+    assert!(!DpSolver.has_solution_at_least(&huge_profits, u64::MAX / 2));
+}
+
+/// Builds a solver with `Default`, and checks that a copy of it solves the input the same way
+fn assert_default_solver<S: KnapsackSolver + Default + Copy>(input: &KnapsackInput) {
+    let solver = S::default();
+    let copy = solver;
+    assert_eq!(solver.solve(input), copy.solve(input), "{}", solver.name());
+}
+
+#[test]
+fn solvers_and_solutions_have_defaults() {
+    // the weights are equal, for the equal weight solver
+    let input = input(&[(3, 4), (3, 5), (3, 6), (3, 3)], 9);
+
+    assert_default_solver::<DpSolver>(&input);
+    assert_default_solver::<BktSolver>(&input);
+    assert_default_solver::<FptasDpSolver>(&input);
+    assert_default_solver::<MinKnapSolver>(&input);
+    assert_default_solver::<EqualWeightSolver>(&input);
+    assert_default_solver::<IddfsBktSolver>(&input);
+    assert_default_solver::<BalKnapSolver>(&input);
+    assert_default_solver::<RandomizedRoundingSolver>(&input);
+
+    let solution = KnapsackSolution::default();
+    assert!(solution.items.is_empty());
+    assert_eq!(solution.total_value, 0);
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn minknap_flags_the_full_core_of_a_strongly_correlated_instance() {
+    let mut rng = Rng(0x881);
+
+    // the profits are the weights plus a constant, and the even weights never fill the odd
+    // capacity, so the bounds never prove the optimality of a solution before the last item
+    let correlated: Vec<(u64, u64)> = (0..50)
+        .map(|_| {
+            let weight = 2 * rng.between(1, 500);
+            (weight, weight + 1)
+        })
+        .collect();
+    let total_weight: u64 = correlated.iter().map(|&(weight, _)| weight).sum();
+    let (_, metrics) =
+        MinKnapSolver.solve_with_metrics(&input(&correlated, (total_weight / 2) | 1));
+    assert!(metrics.full_core, "{:?}", metrics);
+
+    // a single item much more efficient than the others is solved by the break solution alone
+    let mut easy = vec![(10, 1000)];
+    easy.extend((0..50).map(|_| (rng.between(20, 30), rng.between(1, 5))));
+    let (_, metrics) = MinKnapSolver.solve_with_metrics(&input(&easy, 10));
+    assert!(!metrics.full_core, "{:?}", metrics);
+}
+
+#[test]
+fn cached_minknap_at_three_capacities_matches_dp() {
+    let mut rng = Rng(0x888);
+    let items: Vec<(u64, u64)> = (0..30)
+        .map(|_| (rng.between(1, 50), rng.between(1, 50)))
+        .collect();
+    let mut solver = CachedMinKnapSolver::new();
+
+    let mut sweep = input(&items, 1);
+    for capacity in [10, 200, 600] {
+        sweep.set_capacity(capacity).unwrap();
+        let solution = solver.solve(&sweep);
+        assert_eq!(solution.total_value, DpSolver.solve(&sweep).total_value);
+        assert_eq!(solution, MinKnapSolver.solve(&sweep));
+        assert_eq!(solution.verify(&sweep), Ok(()));
+    }
+
+    // changing the items drops the cached ordering
+    let mut changed = items;
+    changed[0] = (1, 1000);
+    let changed = input(&changed, 200);
+    let solution = solver.solve(&changed);
+    assert!(solution.items.contains(&0));
+    assert_eq!(solution.total_value, DpSolver.solve(&changed).total_value);
+}
+
+#[test]
+fn every_method_has_one_registered_solver() {
+    let rounding = RandomizedRoundingSolver::default();
+    let registry: HashMap<KnapsackMethod, &dyn KnapsackSolver> = [
+        &DpSolver as &dyn KnapsackSolver,
+        &BktSolver,
+        &FptasDpSolver,
+        &MinKnapSolver,
+        &EqualWeightSolver,
+        &IddfsBktSolver,
+        &BalKnapSolver,
+        &rounding,
+    ]
+    .into_iter()
+    .map(|solver| (solver.method(), solver))
+    .collect();
+
+    assert_eq!(KnapsackMethod::all().len(), registry.len());
+    for method in KnapsackMethod::all() {
+        assert_eq!(registry[method].method(), *method);
+    }
+    assert!(KnapsackMethod::all().contains(&KnapsackMethod::MinKnap));
+}
+
+/// Claims to be exact, but only ever takes the first item
+struct FirstItemSolver;
+
+impl KnapsackSolver for FirstItemSolver {
+    fn solve(&self, input: &KnapsackInput) -> KnapsackSolution {
+        let first = input.into_iter().next().unwrap();
+        KnapsackSolution {
+            items: vec![0],
+            total_value: first.profit(),
+        }
+    }
+
+    fn method(&self) -> KnapsackMethod {
+        KnapsackMethod::Dp
+    }
+
+    fn name(&self) -> &'static str {
+        "FirstItem"
+    }
+
+    fn capabilities(&self) -> SolverCapabilities {
+        SolverCapabilities::EXACT
+    }
+}
+
+#[test]
+fn comparison_detects_a_wrong_solver() {
+    // {1, 2} is optimal, and the first item alone is a valid but worse solution
+    let input = input(&[(4, 3), (3, 4), (5, 6)], 8);
+    let solvers: [&dyn KnapsackSolver; 3] = [&DpSolver, &FirstItemSolver, &MinKnapSolver];
+
+    let report = compare_solvers(&solvers, &input);
+    assert_eq!(report.best_value, Some(10));
+    assert!(!report.all_agree());
+    let disagreeing: Vec<&str> = report.disagreeing().map(|outcome| outcome.name).collect();
+    assert_eq!(disagreeing, vec!["FirstItem"]);
+
+    let report = compare_solvers(&[&DpSolver, &MinKnapSolver], &input);
+    assert!(report.all_agree());
+}
@@ -0,0 +1,57 @@
+//! Classification and direct solving of the trivial instances
+
+use knapsack::{KnapsackInput, KnapsackItem, KnapsackSolution, TrivialCase};
+
+/// Builds an input from (weight, profit) pairs
+fn input(items: &[(u64, u64)], capacity: u64) -> KnapsackInput {
+    let items = items
+        .iter()
+        .map(|&(weight, profit)| KnapsackItem::new(weight, profit))
+        .collect();
+    KnapsackInput::new(items, capacity, 1).unwrap()
+}
+
+fn solution(items: Vec<usize>, total_value: u64) -> Option<KnapsackSolution> {
+    Some(KnapsackSolution { items, total_value })
+}
+
+#[test]
+fn no_items() {
+    let input = input(&[], 10);
+
+    assert_eq!(input.is_trivial(), Some(TrivialCase::NoItems));
+    assert_eq!(input.solve_trivial(), solution(vec![], 0));
+}
+
+#[test]
+fn zero_profits() {
+    let input = input(&[(20, 0), (30, 0)], 10);
+
+    assert_eq!(input.is_trivial(), Some(TrivialCase::ZeroProfits));
+    assert_eq!(input.solve_trivial(), solution(vec![], 0));
+}
+
+#[test]
+fn all_items_fit() {
+    let input = input(&[(2, 3), (4, 0), (3, 5)], 10);
+
+    assert_eq!(input.is_trivial(), Some(TrivialCase::AllFit));
+    // The zero profit item is left out
+    assert_eq!(input.solve_trivial(), solution(vec![0, 2], 8));
+}
+
+#[test]
+fn single_item() {
+    let input = input(&[(20, 7)], 10);
+
+    assert_eq!(input.is_trivial(), Some(TrivialCase::SingleItem));
+    assert_eq!(input.solve_trivial(), solution(vec![], 0));
+}
+
+#[test]
+fn non_trivial_instance() {
+    let input = input(&[(6, 7), (6, 8)], 10);
+
+    assert_eq!(input.is_trivial(), None);
+    assert_eq!(input.solve_trivial(), None);
+}